@@ -1,7 +1,7 @@
 use std::num::NonZeroU32;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use summary::{Language, Summarizer};
+use summary::{Language, Summarizer, SummarizerBuilder};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     const MAX: NonZeroU32 = NonZeroU32::MAX;
@@ -11,6 +11,19 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("shakespeare", |b| {
         b.iter(|| summarizer.summarize_sentences(black_box(text), MAX))
     });
+
+    // Every term is extracted once, but weighted by the idf, tf-idf, and overall tf-idf passes,
+    // so these are the most sensitive to the cost of tokenizing and stemming.
+    c.bench_function("shakespeare_keywords", |b| {
+        b.iter(|| summarizer.keywords(black_box(text), 10))
+    });
+    let bigrams = SummarizerBuilder::new()
+        .language(Language::English)
+        .ngram(2)
+        .build();
+    c.bench_function("shakespeare_bigrams", |b| {
+        b.iter(|| bigrams.summarize_sentences(black_box(text), MAX))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,36 @@
+//! Summarize text in the browser with `wasm-bindgen`.
+//!
+//! Build with:
+//!
+//! ```sh
+//! cargo build --release --example wasm --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/examples/wasm.wasm
+//! ```
+//!
+//! and then from JavaScript:
+//!
+//! ```js
+//! import init, { summarize } from "./pkg/wasm.js";
+//! await init();
+//! console.log(summarize("See Spot. See Spot run. Run Spot, run!", 2));
+//! ```
+//!
+//! Every feature of the crate builds for `wasm32-unknown-unknown`,
+//! though with `rayon` sentences are scored on a single thread.
+//! For a smaller binary, disable the default features to summarize language agnostically,
+//! without the stemmers and stop words of each language.
+#![cfg(target_arch = "wasm32")]
+
+use std::num::NonZeroU32;
+
+use summary::{Language, Summarizer};
+use wasm_bindgen::prelude::*;
+
+/// Summarize the English text in at most `n` sentences, joined by spaces.
+#[wasm_bindgen]
+pub fn summarize(text: &str, n: u32) -> String {
+    let Some(n) = NonZeroU32::new(n) else {
+        return String::new();
+    };
+    Summarizer::new(Language::English).summarize_to_string(text, n, " ")
+}
@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use std::num::NonZeroU8;
+
+fuzz_target!(|x: (&[u8], NonZeroU8)| {
+    let (data, lines) = x;
+    if let Ok(s) = std::str::from_utf8(data) {
+        use summary::{Language, Summarizer};
+
+        let summarizer = Summarizer::new(Language::English);
+        let summary = summarizer.summarize_markdown(s, lines.into());
+        let _ = std::hint::black_box(summary);
+    }
+});
@@ -0,0 +1,21 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use std::num::NonZeroU8;
+
+fuzz_target!(|x: (&[u8], NonZeroU8)| {
+    let (data, lines) = x;
+    if let Ok(s) = std::str::from_utf8(data) {
+        use summary::{Language, Summarizer};
+
+        let summarizer = Summarizer::new(Language::English);
+        let sentences = summarizer.split_sentences(s);
+        let summary = summarizer.summarize_sentences(s, lines.into());
+        assert!(summary.len() <= usize::from(lines.get()));
+
+        // Every sentence of the summary is one of the text's sentences, in their original order.
+        let mut remaining = sentences.iter();
+        for sentence in summary {
+            assert!(remaining.any(|&other| std::ptr::eq(sentence, other)));
+        }
+    }
+});
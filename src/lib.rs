@@ -8,6 +8,8 @@
 //! # Example
 //! 
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() {
 //! # use summary::{Language, Summarizer};
 //! let summarizer = Summarizer::new(Language::English);
 //! let text = "See Spot. See Spot run. Run Spot, run!";
@@ -15,83 +17,492 @@
 //! for sentence in summarizer.summarize_sentences(text, n) {
 //!     println!("{sentence}");
 //! }
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
-use std::{
-    borrow::Cow,
-    collections::{HashMap, HashSet},
-    num::NonZeroU32,
+//!
+//! # Large documents
+//!
+//! There is no limit on the size of a document beyond what fits in memory.
+//! However, memory use grows with the whole document,
+//! since every sentence keeps its own tf-idf vector until the summary is chosen,
+//! which is typically several times the size of the text itself.
+//! For documents that don't comfortably fit in memory,
+//! [`StreamingSummarizer`] trades some accuracy for bounded memory use.
+//! For documents that grow over time and are summarized repeatedly,
+//! e.g. a live transcript, [`AppendableSummarizer`] avoids re-analyzing the earlier text.
+//!
+//! # Features
+//!
+//! - `std` (default): Support stemming and stop words for each [`Language`].
+//!   Without it, the crate is `no_std` and only summarizes language agnostically.
+//! - `html`: Summarize HTML documents.
+//! - `markdown`: Summarize Markdown documents.
+//! - `rayon`: Score sentences and build IDF models in parallel.
+//! - `serde`: Serialize and deserialize a [`SummarizerConfig`] or an [`IdfModel`].
+//! - `unicode-normalization`: Optionally [normalize](SummarizerBuilder::normalize_unicode) words before stemming.
+//! - `whatlang`: Detect the language of a document.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use core::{
+    cmp::Reverse,
+    fmt,
+    num::{NonZeroU32, NonZeroUsize},
+    ops::Range,
+    str::FromStr,
 };
 
+use foldhash::fast::FixedState;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use unicode_segmentation::UnicodeSegmentation;
 
+// Maps are hashed with a fixed seed, so that they iterate in the same order on every run,
+// and summing over them always rounds the same way. Otherwise, scores could differ by rounding
+// error between runs, and sentences with (nearly) tied scores could be selected inconsistently.
+type HashMap<K, V> = hashbrown::HashMap<K, V, FixedState>;
+type HashSet<T> = hashbrown::HashSet<T, FixedState>;
 type IdfMap = HashMap<Box<str>, f64>;
 
 /// Document summarizer.
+///
+/// A `Summarizer` is `Send + Sync`, so a single instance
+/// may be shared between threads, e.g. behind an [`Arc`],
+/// instead of being rebuilt for every document.
 pub struct Summarizer {
-    stemmer: Stemmer,
-    stop_words: StopWords,
+    analyzer: Analyzer,
+    splitter: Box<dyn SentenceSplitter>,
+    /// A custom similarity function, or `None` for the cosine similarity.
+    similarity: Option<Box<Similarity>>,
+    scoring: ScoringScheme,
+    sublinear_tf: bool,
+    smooth_idf: bool,
+    length_normalization: LengthNormalization,
+    centrality: CentralityMode,
+    numeric_boost: f64,
+    position_boost: Option<PositionBoost>,
+    skip_leading: usize,
+    min_sentence_words: usize,
+    max_stop_word_ratio: f64,
+    dedup_threshold: f64,
+    always_include_core: bool,
+    output_order: OutputOrder,
+    normalize_whitespace: bool,
 }
 
+// Sharing a `Summarizer` or its builder between threads is part of their API.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Summarizer>();
+    assert_send_sync::<SummarizerBuilder>();
+};
+
 impl Summarizer {
     /// Create a new `Summarizer`.
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn new(language: Language) -> Self {
-        let stemmer = Stemmer::new(language);
-        let stop_words = StopWords::new(language);
-        Self {
-            stemmer,
-            stop_words,
-        }
+        SummarizerBuilder::new().language(language).build()
     }
 
     /// Create a new `Summarizer` that is language agnostic.
     pub fn new_language_agnostic() -> Self {
-        let stemmer = Stemmer(None);
-        let stop_words = StopWords(HashSet::new());
-        Self {
-            stemmer,
-            stop_words,
+        SummarizerBuilder::new().build()
+    }
+
+    /// Create a new `Summarizer` for the detected language of the text.
+    ///
+    /// Falls back to a language agnostic `Summarizer`
+    /// if the language can not be reliably detected.
+    #[cfg(feature = "whatlang")]
+    #[must_use]
+    pub fn new_autodetect(text: &str) -> Self {
+        match detect_language(text) {
+            Some(language) => Self::new(language),
+            None => Self::new_language_agnostic(),
         }
     }
 
-    #[inline(never)] // discourage monomorphization bloat
-    fn summarize_indices<'a>(&self, text: &'a str) -> (Vec<&'a str>, Vec<u32>) {
-        assert!(
-            u32::try_from(text.len()).is_ok(),
-            "can not summarize texts longer than 4 GiB"
-        );
+    /// Add stop words, in addition to the ones already in use.
+    ///
+    /// Stop words are matched case-insensitively,
+    /// unless the summarizer was built to be [case sensitive](SummarizerBuilder::case_sensitive).
+    pub fn with_extra_stop_words(&mut self, words: impl IntoIterator<Item = String>) {
+        self.analyzer.stop_words.extend(words);
+    }
+
+    /// Remove a stop word, so that it is no longer ignored.
+    ///
+    /// Stop words are matched case-insensitively,
+    /// unless the summarizer was built to be [case sensitive](SummarizerBuilder::case_sensitive).
+    pub fn remove_stop_word(&mut self, word: &str) {
+        self.analyzer.stop_words.remove(word);
+    }
+
+    /// Add stop phrases, e.g. "for the avoidance of doubt",
+    /// whose words are ignored wherever they all occur consecutively.
+    ///
+    /// Phrases are split into words by the summarizer's tokenizer,
+    /// and their words are matched like stop words, i.e. case-insensitively,
+    /// unless the summarizer was built to be [case sensitive](SummarizerBuilder::case_sensitive).
+    /// Like stop words, stop phrases end any [n-gram](SummarizerBuilder::ngram) which would span them.
+    pub fn with_stop_phrases(&mut self, phrases: impl IntoIterator<Item = String>) {
+        self.analyzer.extend_stop_phrases(phrases);
+    }
+
+    /// Use a custom sentence splitter,
+    /// instead of splitting sentences by the Unicode sentence boundary rules.
+    pub fn with_splitter(&mut self, splitter: impl SentenceSplitter + 'static) {
+        self.splitter = Box::new(splitter);
+    }
+
+    /// Use a custom similarity function between tf-idf vectors,
+    /// instead of the cosine similarity.
+    ///
+    /// The function is used both to choose the "core" sentence,
+    /// i.e. the sentence most similar to the document at large,
+    /// and to score every sentence by its similarity to the "core" sentence.
+    /// Other comparisons, e.g. between [duplicates](SummarizerBuilder::dedup_threshold),
+    /// still use the cosine similarity.
+    /// The function must never return NaN, which is a bug that panics in debug builds.
+    pub fn with_similarity(
+        &mut self,
+        similarity: impl Fn(TermVector<'_>, TermVector<'_>) -> f64 + Send + Sync + 'static,
+    ) {
+        self.similarity = Some(Box::new(similarity));
+    }
+
+    /// Split the text into sentences, exactly as it is split when summarizing.
+    ///
+    /// This uses the custom sentence splitter if one is in use,
+    /// or otherwise the Unicode sentence boundary rules.
+    #[must_use]
+    pub fn split_sentences<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.splitter.split(text)
+    }
+
+    /// The number of sentences in the text, as it is split when summarizing.
+    ///
+    /// This is useful for choosing the length of a summary relative to the text,
+    /// e.g. a tenth of its sentences.
+    #[must_use]
+    pub fn sentence_count(&self, text: &str) -> usize {
+        self.splitter.split(text).len()
+    }
+
+    /// The "core" sentence of the text, i.e. the candidate sentence most similar to the text at large.
+    ///
+    /// This is the sentence around which every summary is gathered,
+    /// and on its own makes a one sentence summary, e.g. for a headline or preview.
+    /// Returns `None` if the text has no candidate sentences, e.g. if it is empty.
+    #[must_use]
+    pub fn core_sentence<'a>(&self, text: &'a str) -> Option<&'a str> {
+        self.score(text).map(|scored| scored.sentences[scored.core])
+    }
 
+    /// Extract the `n` most important keywords from the text,
+    /// sorted by descending weight.
+    ///
+    /// Keywords are stemmed and lowercased (unless case sensitive), and never include stop words.
+    /// With [n-grams](SummarizerBuilder::ngram), keywords may be runs of several words.
+    /// The weight is the keyword's tf-idf weight across the whole text.
+    #[must_use]
+    pub fn keywords(&self, text: &str, n: usize) -> Vec<(String, f64)> {
         let Self {
-            stemmer,
-            stop_words,
+            analyzer, splitter, ..
+        } = self;
+
+        let terms = analyzer.terms(&splitter.split(text));
+        let idfs = idfs(&terms, self.smooth_idf);
+        let weighting = self.weighting(&terms);
+        let overall = tf_idf(&terms, &idfs, weighting);
+
+        let mut keywords = sorted_terms(overall);
+        keywords.truncate(n);
+        keywords
+    }
+
+    /// The tf-idf vector of a sentence, as terms sorted by descending weight.
+    ///
+    /// Terms are stemmed and lowercased (unless case sensitive), and never include stop words.
+    /// The vector is normalized to unit length,
+    /// exactly as it is when scoring sentences,
+    /// unless none of its terms have any weight, in which case it is zero.
+    #[must_use]
+    pub fn sentence_vector(&self, sentence: &str, idf: &IdfModel) -> Vec<(String, f64)> {
+        let terms = self.analyzer.terms(&[sentence]);
+        sorted_terms(self.model_tf_idf(&terms, idf))
+    }
+
+    /// The similarity of two sentences, as it is measured when scoring sentences.
+    ///
+    /// This is the cosine similarity of the sentences' tf-idf vectors,
+    /// which are weighted by the given IDF model.
+    /// The similarity is typically in `0.0..=1.0`, and unlike the scores of sentences,
+    /// e.g. from [`Summarizer::summarize_scored`], it's not adjusted by any boosts.
+    /// With [`ScoringScheme::Bm25`], lengths are normalized by the average of the model's corpus,
+    /// so the similarities of different pairs of sentences are comparable.
+    #[must_use]
+    pub fn sentence_similarity(&self, a: &str, b: &str, idf: &IdfModel) -> f64 {
+        let terms = self.analyzer.terms(&[a, b]);
+        let (a, b) = terms.split_at(1);
+        cosine_compare(&self.model_tf_idf(a, idf), &self.model_tf_idf(b, idf))
+    }
+
+    /// The tf-idf vector of a sentence's terms, weighted by the model.
+    fn model_tf_idf(&self, terms: &[Vec<Box<str>>], idf: &IdfModel) -> IdfMap {
+        // Without the corpus' average length, the sentence is measured against its own length.
+        let weighting = self.weighting_with(|| idf.avg_len.unwrap_or_else(|| average_len(terms)));
+        tf_idf(terms, &idf.idfs, weighting)
+    }
+
+    /// Build an IDF model from a corpus of documents.
+    ///
+    /// Terms are weighted by how many sentences across the whole corpus they appear in,
+    /// so a model built from a single document reproduces
+    /// the weighting [`Summarizer::summarize_sentences`] uses for that document.
+    #[must_use]
+    pub fn build_idf(&self, documents: &[&str]) -> IdfModel {
+        let Self {
+            analyzer, splitter, ..
         } = self;
 
-        let sentences = sentences(text);
+        #[cfg(feature = "rayon")]
+        let sentences: Vec<_> = documents
+            .par_iter()
+            .flat_map_iter(|text| splitter.split(text))
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let sentences: Vec<_> = documents
+            .iter()
+            .flat_map(|text| splitter.split(text))
+            .collect();
+        let terms = analyzer.terms(&sentences);
+        IdfModel {
+            idfs: idfs(&terms, self.smooth_idf),
+            avg_len: Some(average_len(&terms)).filter(|&len| len > 0.0),
+        }
+    }
+
+    fn weighting(&self, terms: &[Vec<Box<str>>]) -> Weighting {
+        self.weighting_with(|| average_len(terms))
+    }
+
+    /// The term weighting options, with the average number of terms per sentence
+    /// only computed if it is needed.
+    fn weighting_with(&self, avg_len: impl FnOnce() -> f64) -> Weighting {
+        let bm25 = match self.scoring {
+            ScoringScheme::TfIdf => None,
+            ScoringScheme::Bm25 { k1, b } => Some(Bm25 {
+                k1,
+                b,
+                avg_len: avg_len(),
+            }),
+        };
+        Weighting {
+            sublinear_tf: self.sublinear_tf,
+            bm25,
+        }
+    }
+
+    fn score<'a>(&self, text: &'a str) -> Option<Scored<'a>> {
+        self.score_split(self.splitter.split(text), 0)
+    }
+
+    /// Score the sentences, where `offset` is the position of the first of them in the document.
+    fn score_split<'a>(&self, sentences: Vec<&'a str>, offset: usize) -> Option<Scored<'a>> {
+        if sentences.len() == 1 && !self.smooth_idf {
+            // Every term of a lone sentence would have an idf of `log2(1 / 1) == 0`,
+            // which is exactly the weight of a term missing from the idfs.
+            return self.score_with_idfs(sentences, &IdfMap::default(), offset);
+        }
+        let terms = self.analyzer.terms(&sentences);
+        let idfs = idfs(&terms, self.smooth_idf);
+        self.score_into(sentences, &terms, &idfs, &mut Vec::new(), offset)
+    }
+
+    fn score_with_idfs<'a>(
+        &self,
+        sentences: Vec<&'a str>,
+        idfs: &IdfMap,
+        offset: usize,
+    ) -> Option<Scored<'a>> {
+        let terms = self.analyzer.terms(&sentences);
+        self.score_into(sentences, &terms, idfs, &mut Vec::new(), offset)
+    }
+
+    /// Score the sentences, given the terms of each of them,
+    /// reusing the allocations of `tf_idfs` for the sentences' vectors.
+    /// `offset` is the position of the first sentence in the document.
+    ///
+    /// The vectors are moved out of `tf_idfs` into the result,
+    /// so `tf_idfs` keeps its allocations only if no sentence is a candidate.
+    #[inline(never)] // discourage monomorphization bloat
+    fn score_into<'a>(
+        &self,
+        sentences: Vec<&'a str>,
+        terms: &[Vec<Box<str>>],
+        idfs: &IdfMap,
+        tf_idfs: &mut Vec<IdfMap>,
+        offset: usize,
+    ) -> Option<Scored<'a>> {
         if sentences.is_empty() {
-            return Default::default();
+            return None;
+        }
+        let weighting = self.weighting(terms);
+        tf_idfs_into(terms, idfs, weighting, tf_idfs);
+        let overall = tf_idf(terms, idfs, weighting);
+
+        let candidates: Vec<_> = (0..sentences.len())
+            .filter(|&i| self.is_candidate(offset + i, sentences[i], &terms[i]))
+            .collect();
+        if candidates.is_empty() {
+            return None;
         }
-        let idfs = idfs(&sentences, stop_words, stemmer);
-        let tf_idfs = tf_idfs(&sentences, &idfs, stop_words, stemmer);
-        let overall = tf_idf(&sentences, &idfs, stop_words, stemmer);
+        self.score_vectors(sentences, core::mem::take(tf_idfs), overall, candidates)
+    }
 
-        let i = tf_idfs
+    /// Score the sentences, given their tf-idf vectors, that of the whole document,
+    /// and the indices of the candidate sentences.
+    fn score_vectors<'a>(
+        &self,
+        sentences: Vec<&'a str>,
+        tf_idfs: Vec<IdfMap>,
+        overall: IdfMap,
+        candidates: Vec<usize>,
+    ) -> Option<Scored<'a>> {
+        let i = candidates
             .iter()
-            .enumerate()
-            .map(|(i, tf_idf)| (i, OrdFloat(cosine_compare(tf_idf, &overall))))
-            .max_by_key(|(_, x)| *x)
-            .unwrap()
+            .map(|&i| (i, OrdFloat::new(self.similarity(&tf_idfs[i], &overall))))
+            .max_by_key(|&(i, x)| (x, Reverse(i)))?
             .0;
 
         let best_match = &tf_idfs[i];
 
-        let mut indices: Vec<_> = (0..u32::try_from(tf_idfs.len()).unwrap()).collect();
-        indices.sort_unstable_by_key(|&i| {
-            let i = usize::try_from(i).unwrap();
-            let tf_idf = &tf_idfs[i];
-            OrdFloat(-cosine_compare(tf_idf, best_match))
-        });
+        // Iterating over `best_match` and `overall` fixes the summation order for every sentence,
+        // so identical sentences are guaranteed to receive identical scores.
+        let len = sentences.len();
+        let scores = tf_idfs
+            .iter()
+            .zip(&sentences)
+            .enumerate()
+            .map(|(position, (tf_idf, sentence))| {
+                let relevance = self.relevance(best_match, &overall, tf_idf, sentence);
+                relevance * self.position_multiplier(position, len)
+            })
+            .collect();
+
+        Some(Scored {
+            sentences,
+            tf_idfs,
+            overall,
+            candidates,
+            core: i,
+            core_first: self.always_include_core,
+            scores,
+        })
+    }
+
+    /// The score of a sentence, given the tf-idf vectors of the "core" sentence and the document.
+    fn relevance(
+        &self,
+        best_match: &IdfMap,
+        overall: &IdfMap,
+        tf_idf: &IdfMap,
+        sentence: &str,
+    ) -> f64 {
+        let mut score = match self.centrality {
+            CentralityMode::CoreSentence => self.similarity(best_match, tf_idf),
+            CentralityMode::Document => self.similarity(overall, tf_idf),
+            CentralityMode::Blend(weight) => {
+                weight * self.similarity(overall, tf_idf)
+                    + (1.0 - weight) * self.similarity(best_match, tf_idf)
+            }
+        };
+        if let normalization @ (LengthNormalization::Sqrt | LengthNormalization::Log) =
+            self.length_normalization
+        {
+            let len = self.analyzer.tokenizer.tokenize(sentence).len();
+            score /= normalization.divisor(len);
+        }
+        if has_number(sentence) {
+            score *= self.numeric_boost;
+        }
+        score
+    }
+
+    /// The multiplier of the score of the sentence at `position` among `len` sentences.
+    fn position_multiplier(&self, position: usize, len: usize) -> f64 {
+        let Some(PositionBoost {
+            fraction,
+            magnitude,
+        }) = self.position_boost
+        else {
+            return 1.0;
+        };
+        if len <= 1 {
+            return 1.0 + magnitude;
+        }
+        // The relative distance to the nearest end, from 0.0 (first or last) to 0.5 (middle).
+        let x = position as f64 / (len - 1) as f64;
+        let distance = f64::min(x, 1.0 - x);
+        if distance < fraction {
+            1.0 + magnitude * (1.0 - distance / fraction)
+        } else {
+            1.0
+        }
+    }
+
+    /// The similarity of two tf-idf vectors.
+    fn similarity(&self, a: &IdfMap, b: &IdfMap) -> f64 {
+        match &self.similarity {
+            Some(similarity) => similarity(TermVector(a), TermVector(b)),
+            None => cosine_compare(a, b),
+        }
+    }
+
+    /// Whether the sentence at `position`, with the given terms, may be selected for a summary.
+    ///
+    /// Sentences which are not candidates still contribute to the document's term statistics,
+    /// including to the number of sentences by which document frequencies are divided.
+    fn is_candidate(&self, position: usize, sentence: &str, terms: &[Box<str>]) -> bool {
+        if position < self.skip_leading {
+            return false;
+        }
+        // Pseudo-sentences such as "42." or "..." have no content to summarize,
+        // and only tie with each other at the bottom of the ranking.
+        if !terms
+            .iter()
+            .any(|term| term.chars().any(char::is_alphabetic))
+        {
+            return false;
+        }
+        if self.min_sentence_words == 0 && self.max_stop_word_ratio >= 1.0 {
+            return true;
+        }
+        // Words are counted as when scoring, i.e. by the (possibly custom) tokenizer.
+        let words = self.analyzer.tokenizer.tokenize(sentence);
+        if words.len() < self.min_sentence_words {
+            return false;
+        }
+        if self.max_stop_word_ratio < 1.0 {
+            let stop_words = words
+                .iter()
+                .filter(|word| self.analyzer.stop_words.contains(word))
+                .count();
+            if stop_words as f64 > self.max_stop_word_ratio * words.len() as f64 {
+                return false;
+            }
+        }
+        true
+    }
 
-        (sentences, indices)
+    fn summarize_indices<'a>(&self, text: &'a str) -> (Vec<&'a str>, Vec<usize>, Vec<f64>) {
+        rank(self.score(text))
     }
 
     /// Provide a summary for the text, reduced by a given ratio.
@@ -100,116 +511,2952 @@ impl Summarizer {
     /// An attempt will be made to return a summary that is
     /// as close to the ratio as possible without exceeding it.
     /// However if this would result in 0 sentences,
-    /// the summary is rounded up to 1 sentence.
+    /// the summary is rounded up to 1 sentence,
+    /// so the summary is only empty if no sentence may be selected,
+    /// e.g. if the text is empty, or only has stop words and numbers.
+    /// See [`Summarizer::summarize_ratio_strict`] for a summary which never exceeds the ratio.
     ///
     /// # Panics
     ///
-    /// Panics if the provided text is longer than 4 GiB,
-    /// or if the provided ratio is not in `0.0..=1.0`.
+    /// Panics if the provided ratio is not in `0.0..=1.0`.
     #[must_use]
     pub fn summarize_ratio<'a>(&self, text: &'a str, ratio: f64) -> Vec<&'a str> {
-        assert!((0.0..=1.0).contains(&ratio));
-        let (sentences, mut indices) = self.summarize_indices(text);
-        if sentences.is_empty() {
-            return Vec::new();
-        }
+        self.try_summarize_ratio(text, ratio)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        let target = (ratio * (text.len() as f64)).round() as usize;
-        let mut total_len = 0;
-        let end = indices
-            .iter()
-            .enumerate()
-            .find_map(|(i, &j)| {
-                let j = usize::try_from(j).unwrap();
-                total_len += sentences[j].trim_end().len() + 1;
-                if total_len > target {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(indices.len())
-            .max(1);
-        indices.truncate(end);
+    /// Provide a summary for the text, reduced by a given ratio.
+    ///
+    /// This is the non-panicking version of [`Summarizer::summarize_ratio`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provided ratio is not in `0.0..=1.0`.
+    pub fn try_summarize_ratio<'a>(
+        &self,
+        text: &'a str,
+        ratio: f64,
+    ) -> Result<Vec<&'a str>, SummarizeError> {
+        let (sentences, indices) = self.ratio_selection(text, ratio, 1)?;
+        Ok(summarize_impl(sentences, indices, self.output_order))
+    }
+
+    /// Provide a summary for the text, reduced by a given ratio, which never exceeds the ratio.
+    ///
+    /// This is like [`Summarizer::summarize_ratio`],
+    /// except that the summary is never rounded up to 1 sentence,
+    /// so e.g. a ratio of 0.0 always returns an empty summary,
+    /// as does a ratio too small for even the most relevant sentence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided ratio is not in `0.0..=1.0`.
+    #[must_use]
+    pub fn summarize_ratio_strict<'a>(&self, text: &'a str, ratio: f64) -> Vec<&'a str> {
+        self.try_summarize_ratio_strict(text, ratio)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        summarize_impl(sentences, indices)
+    /// Provide a summary for the text, reduced by a given ratio, which never exceeds the ratio.
+    ///
+    /// This is the non-panicking version of [`Summarizer::summarize_ratio_strict`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provided ratio is not in `0.0..=1.0`.
+    pub fn try_summarize_ratio_strict<'a>(
+        &self,
+        text: &'a str,
+        ratio: f64,
+    ) -> Result<Vec<&'a str>, SummarizeError> {
+        let (sentences, indices) = self.ratio_selection(text, ratio, 0)?;
+        Ok(summarize_impl(sentences, indices, self.output_order))
     }
 
-    /// Provide a `n` sentence summary for the text.
+    /// Provide a summary for the text, reduced by a given ratio,
+    /// but of at least `min_sentences` and at most `max_sentences` sentences.
     ///
-    /// If the text is not longer than `n` sentences,
-    /// the entire text is returned.
+    /// The ratio is applied exactly as in [`Summarizer::summarize_ratio`],
+    /// and then the number of sentences is clamped to `min_sentences..=max_sentences`,
+    /// or to the number of sentences in the text if it has fewer than `min_sentences`.
     ///
     /// # Panics
     ///
-    /// Panics if the provided text is longer than 4 GiB.
+    /// Panics if the provided ratio is not in `0.0..=1.0`,
+    /// or if `min_sentences` is greater than `max_sentences`.
     #[must_use]
-    pub fn summarize_sentences<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
-        let (sentences, mut indices) = self.summarize_indices(text);
-        if sentences.is_empty() {
-            return Vec::new();
+    pub fn summarize_ratio_clamped<'a>(
+        &self,
+        text: &'a str,
+        ratio: f64,
+        min_sentences: usize,
+        max_sentences: usize,
+    ) -> Vec<&'a str> {
+        assert!(
+            min_sentences <= max_sentences,
+            "min_sentences must be at most max_sentences"
+        );
+        let (sentences, mut indices) = self
+            .ratio_selection(text, ratio, min_sentences.max(1))
+            .unwrap_or_else(|err| panic!("{err}"));
+        indices.truncate(max_sentences);
+        summarize_impl(sentences, indices, self.output_order)
+    }
+
+    /// The sentences of the text, and the ranked indices of those
+    /// which fit in a summary reduced by the given ratio, but at least `min` of them.
+    fn ratio_selection<'a>(
+        &self,
+        text: &'a str,
+        ratio: f64,
+        min: usize,
+    ) -> Result<(Vec<&'a str>, Vec<usize>), SummarizeError> {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(SummarizeError::InvalidRatio);
         }
-        indices.truncate(n.get().try_into().unwrap());
-        summarize_impl(sentences, indices)
+        let Some(scored) = self.score(text) else {
+            return Ok(Default::default());
+        };
+
+        let target = round(ratio * (text.len() as f64)) as usize;
+        let indices = self.select_budget(&scored, target, min, |s| s.trim_end().len() + 1);
+        Ok((scored.sentences, indices))
     }
-}
 
-struct Stemmer(Option<rust_stemmers::Stemmer>);
+    /// Provide a summary for the text, reduced to a given percentage.
+    ///
+    /// This is equivalent to [`Summarizer::summarize_ratio`] with a ratio of `percent / 100`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided percentage is greater than 100.
+    #[must_use]
+    pub fn summarize_percent<'a>(&self, text: &'a str, percent: u8) -> Vec<&'a str> {
+        assert!(percent <= 100, "percentage must be at most 100");
+        self.summarize_ratio(text, f64::from(percent) / 100.0)
+    }
 
-impl Stemmer {
-    fn new(language: Language) -> Self {
-        use rust_stemmers::Algorithm;
+    /// Provide a summary for the text, limited to `n` words.
+    ///
+    /// Words are counted by the [tokenizer](SummarizerBuilder::tokenizer), the same way
+    /// they are counted when scoring sentences and for [`SummarizerBuilder::min_sentence_words`].
+    /// An attempt will be made to return a summary that is
+    /// as close to `n` words as possible without exceeding it.
+    /// However if this would result in 0 sentences,
+    /// i.e. the most relevant sentence alone is longer than `n` words,
+    /// the summary is rounded up to 1 sentence and exceeds the budget.
+    /// The summary is empty if no sentence may be selected,
+    /// as for [`Summarizer::summarize_sentences`].
+    #[must_use]
+    pub fn summarize_words<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        let Some(scored) = self.score(text) else {
+            return Vec::new();
+        };
+        let target = saturating_usize(n);
+        let indices = self.select_budget(&scored, target, 1, |s| {
+            self.analyzer.tokenizer.tokenize(s).len()
+        });
+        summarize_impl(scored.sentences, indices, self.output_order)
+    }
 
-        #[rustfmt::skip]
-        let algo = match language {
-            Language::Arabic     => Algorithm::Arabic,
-            Language::Danish     => Algorithm::Danish,
-            Language::Dutch      => Algorithm::Dutch,
-            Language::English    => Algorithm::English,
-            Language::Finnish    => Algorithm::Finnish,
-            Language::French     => Algorithm::French,
-            Language::German     => Algorithm::German,
-            Language::Greek      => Algorithm::Greek,
-            Language::Hungarian  => Algorithm::Hungarian,
-            Language::Italian    => Algorithm::Italian,
-            Language::Norwegian  => Algorithm::Norwegian,
-            Language::Portuguese => Algorithm::Portuguese,
-            Language::Romanian   => Algorithm::Romanian,
-            Language::Russian    => Algorithm::Russian,
-            Language::Spanish    => Algorithm::Spanish,
-            Language::Swedish    => Algorithm::Swedish,
-            Language::Tamil      => Algorithm::Tamil,
-            Language::Turkish    => Algorithm::Turkish,
-            _ => {
-                return Self(None);
-            }
+    /// Provide a summary for the text, limited to `max_graphemes` user-perceived characters.
+    ///
+    /// Characters are counted as extended grapheme clusters,
+    /// excluding the whitespace surrounding each sentence,
+    /// so e.g. CJK characters and emoji sequences each count once.
+    /// An attempt will be made to return a summary that is
+    /// as close to `max_graphemes` as possible without exceeding it.
+    /// However if this would result in 0 sentences,
+    /// the summary is rounded up to 1 sentence and exceeds the budget.
+    #[must_use]
+    pub fn summarize_grapheme_budget<'a>(
+        &self,
+        text: &'a str,
+        max_graphemes: usize,
+    ) -> Vec<&'a str> {
+        let Some(scored) = self.score(text) else {
+            return Vec::new();
         };
-        Self(Some(rust_stemmers::Stemmer::create(algo)))
+        let indices = self.select_budget(&scored, max_graphemes, 1, |s| {
+            s.trim().graphemes(true).count()
+        });
+        summarize_impl(scored.sentences, indices, self.output_order)
     }
 
-    fn stem(&self, s: &str) -> Box<str> {
-        let tmp: Cow<str>;
-        let s = if let Some(stemmer) = &self.0 {
-            tmp = stemmer.stem(s);
-            &tmp
+    /// Provide a `n` sentence summary for the text.
+    ///
+    /// If the text is not longer than `n` sentences,
+    /// every sentence which may be selected is returned.
+    /// The sentences are returned in the [output order](SummarizerBuilder::output_order),
+    /// i.e. by default in document order.
+    /// Fewer than `n` sentences may be returned if some are
+    /// [too short](SummarizerBuilder::min_sentence_words), [skipped](SummarizerBuilder::skip_leading)
+    /// or [duplicates](SummarizerBuilder::dedup_threshold);
+    /// see [`Summarizer::summarize_sentences_filled`] for a predictable count.
+    /// Sentences without any words other than stop words and numbers,
+    /// e.g. "42." or "...", are never selected,
+    /// so the summary is empty if the text has no other sentences.
+    ///
+    /// Ties are broken in favor of earlier sentences,
+    /// and identical sentences are duplicates by default,
+    /// so e.g. summarizing "Run. Run. Run." always returns only the first "Run.".
+    #[must_use]
+    pub fn summarize_sentences<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        self.summarize_deduped(self.score(text), n)
+    }
+
+    /// Provide a `n` sentence summary for the text, as possibly owned strings.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`],
+    /// and each is borrowed from the text unless it had to be transformed,
+    /// so that callers which may receive transformed sentences
+    /// need not copy the ones which were not.
+    /// With [normalized whitespace](SummarizerBuilder::normalize_whitespace),
+    /// a sentence is only copied if it has whitespace to collapse within it.
+    #[must_use]
+    pub fn summarize_sentences_cow<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<Cow<'a, str>> {
+        self.summarize_sentences(text, n)
+            .into_iter()
+            .map(|sentence| {
+                if self.normalize_whitespace {
+                    collapse_whitespace(sentence)
+                } else {
+                    Cow::Borrowed(sentence)
+                }
+            })
+            .collect()
+    }
+
+    /// Provide a `n` sentence summary for the text, along with statistics about it.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`].
+    #[must_use]
+    pub fn summarize_sentences_with_stats<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+    ) -> (Vec<&'a str>, SummaryStats) {
+        let sentences = self.summarize_sentences(text, n);
+        let mut stats = SummaryStats {
+            sentences: sentences.len(),
+            ..SummaryStats::default()
+        };
+        for sentence in &sentences {
+            stats.words += sentence.unicode_words().count();
+            stats.graphemes += sentence.graphemes(true).count();
+            stats.bytes += sentence.len();
+        }
+        (sentences, stats)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// along with the fraction of the text's weighted terms which it covers.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`].
+    /// The coverage is the total tf-idf weight in the whole text of the terms
+    /// which appear in the summary, relative to that of all terms of the text.
+    /// It is in `0.0..=1.0`, and is 0.0 if the text has no weighted terms.
+    #[must_use]
+    pub fn summarize_with_coverage<'a>(&self, text: &'a str, n: NonZeroU32) -> (Vec<&'a str>, f64) {
+        let Some(scored) = self.score(text) else {
+            return (Vec::new(), 0.0);
+        };
+        let indices = self.select(&scored, n);
+        let covered: HashSet<_> = indices
+            .iter()
+            .flat_map(|&i| scored.tf_idfs[i].keys())
+            .collect();
+        let total: f64 = scored.overall.values().sum();
+        let coverage = if total > 0.0 {
+            let weight: f64 = covered.into_iter().map(|term| scored.overall[term]).sum();
+            (weight / total).min(1.0)
         } else {
-            s
+            0.0
         };
-        s.to_lowercase().into_boxed_str()
+        (
+            summarize_impl(scored.sentences, indices, self.output_order),
+            coverage,
+        )
     }
-}
 
-#[derive(Default)]
-struct StopWords(HashSet<Box<str>>);
+    /// Provide a summary of exactly `n` sentences for the text,
+    /// or the entire text if it is not longer than `n` sentences.
+    ///
+    /// This is like [`Summarizer::summarize_sentences`], except that sentences
+    /// removed as duplicates are replaced by the next highest ranked sentences.
+    /// Only if there are still too few sentences are the ones which were
+    /// filtered out as duplicates or non-candidates used to top up the summary,
+    /// again by descending score, though [skipped](SummarizerBuilder::skip_leading)
+    /// sentences never are.
+    /// The selected sentences are returned in the [output order](SummarizerBuilder::output_order).
+    #[must_use]
+    pub fn summarize_sentences_filled<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        let n = saturating_usize(n);
+        let Some(scored) = self.score(text) else {
+            // No sentence is a candidate, so they are all equally (ir)relevant.
+            let mut sentences = self.split_sentences(text);
+            sentences.drain(..self.skip_leading.min(sentences.len()));
+            sentences.truncate(n);
+            return sentences;
+        };
+        let mut indices = scored.ranking();
+        dedup(
+            &scored.sentences,
+            &scored.tf_idfs,
+            &mut indices,
+            self.dedup_threshold,
+        );
+        indices.truncate(n);
+        if indices.len() < n {
+            let mut selected = vec![false; scored.sentences.len()];
+            for &i in &indices {
+                selected[i] = true;
+            }
+            let mut rest: Vec<_> = (self.skip_leading..scored.sentences.len())
+                .filter(|&i| !selected[i])
+                .collect();
+            sort_by_score(&mut rest, &scored.scores);
+            rest.truncate(n - indices.len());
+            indices.extend(rest);
+        }
+        summarize_impl(scored.sentences, indices, self.output_order)
+    }
 
-impl StopWords {
-    fn new(language: Language) -> Self {
-        use stop_words::LANGUAGE as Dict;
+    /// Provide a `n` sentence summary for the text,
+    /// reusing the allocations of a scratch buffer.
+    ///
+    /// This is equivalent to [`Summarizer::summarize_sentences`],
+    /// but when summarizing many documents with the same scratch buffer,
+    /// the buffer is cleared rather than reallocated for each of them.
+    #[must_use]
+    pub fn summarize_sentences_with_scratch<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        scratch: &mut SummarizeScratch,
+    ) -> Vec<&'a str> {
+        let SummarizeScratch { idfs, tf_idfs } = scratch;
 
-        #[rustfmt::skip]
-        let lang = match language {
-            Language::Afrikaans  => Dict::Afrikaans,
-            Language::Arabic     => Dict::Arabic,
+        let sentences = self.splitter.split(text);
+        let terms = self.analyzer.terms(&sentences);
+        idfs_into(&terms, self.smooth_idf, idfs);
+        let Some(scored) = self.score_into(sentences, &terms, idfs, tf_idfs, 0) else {
+            return Vec::new();
+        };
+        let indices = self.select(&scored, n);
+        let Scored {
+            sentences,
+            tf_idfs: buffer,
+            ..
+        } = scored;
+        *tf_idfs = buffer;
+        summarize_impl(sentences, indices, self.output_order)
+    }
+
+    /// Provide a `n` sentence summary of a text which has already been split into sentences.
+    ///
+    /// This behaves like [`Summarizer::summarize_sentences`],
+    /// except that the given sentences are used as-is instead of splitting a text.
+    /// The selected sentences are returned in the [output order](SummarizerBuilder::output_order).
+    #[must_use]
+    pub fn summarize_presplit<'a>(&self, sentences: &[&'a str], n: NonZeroU32) -> Vec<&'a str> {
+        self.summarize_deduped(self.score_split(sentences.to_vec(), 0), n)
+    }
+
+    /// Provide a single `n` sentence summary of a cluster of related documents,
+    /// with each sentence paired with the index of the document it came from.
+    ///
+    /// The sentences of all documents are pooled, weighted by their term statistics
+    /// across the whole cluster, and ranked together around a single "core" sentence,
+    /// so that a sentence repeated in several documents is only selected once.
+    /// The selected sentences are returned in the order of the documents,
+    /// and within each document in their original order.
+    #[must_use]
+    pub fn summarize_documents<'a>(
+        &self,
+        docs: &[&'a str],
+        n: NonZeroU32,
+    ) -> Vec<(usize, &'a str)> {
+        let mut sentences = Vec::new();
+        let mut documents = Vec::new();
+        for (i, doc) in docs.iter().enumerate() {
+            for sentence in self.splitter.split(doc) {
+                sentences.push(sentence);
+                documents.push(i);
+            }
+        }
+        let Some(scored) = self.score_split(sentences, 0) else {
+            return Vec::new();
+        };
+        let mut indices = self.select(&scored, n);
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .map(|i| (documents[i], scored.sentences[i]))
+            .collect()
+    }
+
+    fn summarize_deduped<'a>(&self, scored: Option<Scored<'a>>, n: NonZeroU32) -> Vec<&'a str> {
+        let Some(scored) = scored else {
+            return Vec::new();
+        };
+        let indices = self.select(&scored, n);
+        summarize_impl(scored.sentences, indices, self.output_order)
+    }
+
+    /// The indices of the `n` highest ranked sentences, without duplicates.
+    fn select(&self, scored: &Scored<'_>, n: NonZeroU32) -> Vec<usize> {
+        self.select_reporting(scored, n).0
+    }
+
+    /// Like [`Summarizer::select`], but also returns the duplicates which were removed.
+    fn select_reporting(
+        &self,
+        scored: &Scored<'_>,
+        n: NonZeroU32,
+    ) -> (Vec<usize>, Vec<(usize, usize, f64)>) {
+        scored.select(scored.ranking(), n, self.dedup_threshold)
+    }
+
+    /// The indices of the highest ranked sentences, without duplicates,
+    /// whose total cost fits in `budget`, but of at least `min` sentences.
+    ///
+    /// Duplicates are skipped without counting towards the budget.
+    fn select_budget(
+        &self,
+        scored: &Scored<'_>,
+        budget: usize,
+        min: usize,
+        cost: impl Fn(&str) -> usize,
+    ) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut total = 0;
+        for i in scored.ranking() {
+            let duplicate = duplicate_of(
+                &scored.sentences,
+                &scored.tf_idfs,
+                &indices,
+                i,
+                self.dedup_threshold,
+            );
+            if duplicate.is_some() {
+                continue;
+            }
+            total += cost(scored.sentences[i]);
+            if total > budget && indices.len() >= min {
+                break;
+            }
+            indices.push(i);
+        }
+        indices
+    }
+
+    /// Prepare the text to be summarized repeatedly, e.g. with different lengths.
+    ///
+    /// The text is split into sentences and scored once,
+    /// so that each summary of the [`PreparedDocument`] only has to select sentences.
+    #[must_use]
+    pub fn prepare<'a>(&self, text: &'a str) -> PreparedDocument<'a> {
+        let scored = self.score(text);
+        let ranking = scored.as_ref().map(Scored::ranking).unwrap_or_default();
+        PreparedDocument {
+            scored,
+            ranking,
+            dedup_threshold: self.dedup_threshold,
+            output_order: self.output_order,
+        }
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// along with a report of the sentences which were dropped as duplicates.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`].
+    /// Each entry of the report is a `(kept, dropped, similarity)` tuple,
+    /// where `dropped` is the index of a sentence which was among the `n` highest ranked,
+    /// but was dropped as a [duplicate](SummarizerBuilder::dedup_threshold)
+    /// of the higher ranked sentence at index `kept`,
+    /// with the given similarity between them.
+    /// Indices are zero-based among the text's sentences,
+    /// and the report is empty if no sentences were dropped.
+    #[must_use]
+    pub fn summarize_sentences_with_report<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+    ) -> (Vec<&'a str>, Vec<(usize, usize, f64)>) {
+        let Some(scored) = self.score(text) else {
+            return Default::default();
+        };
+        let (indices, duplicates) = self.select_reporting(&scored, n);
+        (
+            summarize_impl(scored.sentences, indices, self.output_order),
+            duplicates,
+        )
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// with each sentence paired with its zero-based index among the text's sentences.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`],
+    /// in the same order.
+    #[must_use]
+    pub fn summarize_sentences_indexed<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+    ) -> Vec<(usize, &'a str)> {
+        self.summarize_sentences_iter_indexed(text, n).collect()
+    }
+
+    /// Provide a `n` sentence summary for the text, as a lazy iterator.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`],
+    /// in the same order.
+    /// Note that the sentences must all be ranked before the first one can be yielded,
+    /// so the cost of summarizing is paid up front, not as the iterator is advanced.
+    pub fn summarize_sentences_iter<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+    ) -> impl Iterator<Item = &'a str> {
+        self.summarize_sentences_iter_indexed(text, n)
+            .map(|(_, sentence)| sentence)
+    }
+
+    fn summarize_sentences_iter_indexed<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+    ) -> impl Iterator<Item = (usize, &'a str)> {
+        let selected = self.score(text).map(|scored| {
+            let mut indices = self.select(&scored, n);
+            if self.output_order == OutputOrder::Document {
+                indices.sort_unstable();
+            }
+            (scored.sentences, indices)
+        });
+        selected
+            .into_iter()
+            .flat_map(|(sentences, indices)| indices.into_iter().map(move |i| (i, sentences[i])))
+    }
+
+    /// Provide a summary for the text, of every sentence
+    /// with at least the given similarity to the "core" sentence.
+    ///
+    /// This adapts the length of the summary to the text,
+    /// i.e. a focused text has a shorter summary than a diffuse one.
+    /// The similarity is clamped to `0.0..=1.0`, and 1.0 selects only the "core" sentence
+    /// and any sentences identical to it in their terms,
+    /// unless they are dropped as [duplicates](SummarizerBuilder::dedup_threshold).
+    /// With another [`CentralityMode`], the similarity is instead to what that mode ranks against.
+    #[must_use]
+    pub fn summarize_threshold<'a>(&self, text: &'a str, min_similarity: f64) -> Vec<&'a str> {
+        // Absorbs rounding error, so that the "core" sentence has a similarity of at least 1.0.
+        const TOLERANCE: f64 = 1e-9;
+
+        let Some(Scored {
+            sentences,
+            tf_idfs,
+            candidates,
+            scores,
+            ..
+        }) = self.score(text)
+        else {
+            return Vec::new();
+        };
+        let min_similarity = min_similarity.clamp(0.0, 1.0);
+        let mut indices: Vec<_> = candidates
+            .into_iter()
+            .filter(|&i| scores[i] + TOLERANCE >= min_similarity)
+            .collect();
+        sort_by_score(&mut indices, &scores);
+        dedup(&sentences, &tf_idfs, &mut indices, self.dedup_threshold);
+        summarize_impl(sentences, indices, self.output_order)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// using a precomputed IDF model instead of the text's own term statistics.
+    ///
+    /// This is useful for short documents, where the text alone
+    /// is too small to meaningfully weight its terms.
+    /// Terms which do not appear in the model are given no weight.
+    #[must_use]
+    pub fn summarize_sentences_with_idf<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        idf: &IdfModel,
+    ) -> Vec<&'a str> {
+        let scored = self.score_with_idfs(self.splitter.split(text), &idf.idfs, 0);
+        self.summarize_deduped(scored, n)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// biased towards sentences near the start of the text.
+    ///
+    /// Each sentence's relevance is increased by `lead_bias / (position + 1)`
+    /// before ranking, where `position` is the zero-based index of the sentence.
+    /// With `lead_bias == 0.0` this is equivalent to [`Summarizer::summarize_sentences`].
+    #[must_use]
+    pub fn summarize_sentences_biased<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        lead_bias: f64,
+    ) -> Vec<&'a str> {
+        let mut scored = self.score(text);
+        if let Some(Scored { scores, .. }) = &mut scored {
+            for (position, score) in scores.iter_mut().enumerate() {
+                *score += lead_bias / (position + 1) as f64;
+            }
+        }
+        self.summarize_deduped(scored, n)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// with each sentence's relevance multiplied by an external weight before ranking.
+    ///
+    /// `weights[i]` is the weight of the `i`th sentence of [`Summarizer::split_sentences`],
+    /// e.g. greater than 1.0 for sentences a reader highlighted.
+    /// Weights must not be NaN. With every weight 1.0
+    /// this is equivalent to [`Summarizer::summarize_sentences`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SummarizeError::WeightCount`] if there isn't exactly one weight per sentence.
+    pub fn summarize_weighted<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        weights: &[f64],
+    ) -> Result<Vec<&'a str>, SummarizeError> {
+        let sentences = self.splitter.split(text);
+        if weights.len() != sentences.len() {
+            return Err(SummarizeError::WeightCount);
+        }
+        let mut scored = self.score_split(sentences, 0);
+        if let Some(Scored { scores, .. }) = &mut scored {
+            for (score, weight) in scores.iter_mut().zip(weights) {
+                *score *= weight;
+            }
+        }
+        Ok(self.summarize_deduped(scored, n))
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// of the sentences most relevant to the query.
+    ///
+    /// This is equivalent to [`Summarizer::summarize_query_blended`] with a `weight` of 1.0.
+    #[must_use]
+    pub fn summarize_query<'a>(&self, text: &'a str, query: &str, n: NonZeroU32) -> Vec<&'a str> {
+        self.summarize_query_blended(text, query, n, 1.0)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// biased towards sentences relevant to the query.
+    ///
+    /// Each sentence is scored by `weight * query_relevance + (1 - weight) * relevance`,
+    /// where query relevance is the similarity to the query
+    /// (weighted by the term statistics of the text),
+    /// and relevance is the similarity to the "core" sentence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided weight is not in `0.0..=1.0`.
+    #[must_use]
+    pub fn summarize_query_blended<'a>(
+        &self,
+        text: &'a str,
+        query: &str,
+        n: NonZeroU32,
+        weight: f64,
+    ) -> Vec<&'a str> {
+        assert!((0.0..=1.0).contains(&weight));
+        let Self {
+            analyzer, splitter, ..
+        } = self;
+
+        let sentences = splitter.split(text);
+        let terms = analyzer.terms(&sentences);
+        let idfs = idfs(&terms, self.smooth_idf);
+        let weighting = self.weighting(&terms);
+        let query = tf_idf(&analyzer.terms(&[query]), &idfs, weighting);
+        let mut scored = self.score_into(sentences, &terms, &idfs, &mut Vec::new(), 0);
+        if let Some(scored) = &mut scored {
+            scored.blend(&query, weight);
+        }
+        self.summarize_deduped(scored, n)
+    }
+
+    /// Provide a `n` sentence summary for the body of a document,
+    /// biased towards sentences relevant to the document's title.
+    ///
+    /// This is equivalent to [`Summarizer::summarize_query_blended`]
+    /// with the title as the query and a `weight` of 0.5,
+    /// i.e. relevance to the title and to the "core" sentence are weighted equally.
+    /// Use [`Summarizer::summarize_query_blended`] directly to tune the weight.
+    #[must_use]
+    pub fn summarize_with_title<'a>(
+        &self,
+        title: &str,
+        body: &'a str,
+        n: NonZeroU32,
+    ) -> Vec<&'a str> {
+        self.summarize_query_blended(body, title, n, 0.5)
+    }
+
+    /// Provide a summary for the text with `per` sentences from each paragraph.
+    ///
+    /// Paragraphs are separated by blank lines,
+    /// and the sentences of each paragraph are ranked independently of other paragraphs,
+    /// so [duplicates](SummarizerBuilder::dedup_threshold) are only dropped within a paragraph.
+    /// Terms are weighted either by their statistics within the paragraph,
+    /// or within the whole text, depending on `idf`.
+    #[must_use]
+    pub fn summarize_per_paragraph<'a>(
+        &self,
+        text: &'a str,
+        per: NonZeroU32,
+        idf: IdfScope,
+    ) -> Vec<&'a str> {
+        let Self {
+            analyzer, splitter, ..
+        } = self;
+
+        let paragraphs: Vec<_> = paragraphs(text)
+            .into_iter()
+            .map(|paragraph| splitter.split(paragraph))
+            .collect();
+        let document_idfs = match idf {
+            IdfScope::Paragraph => None,
+            IdfScope::Document => {
+                Some(idfs(&analyzer.terms(&paragraphs.concat()), self.smooth_idf))
+            }
+        };
+
+        let mut summary = Vec::new();
+        let mut offset = 0;
+        for sentences in paragraphs {
+            let len = sentences.len();
+            let scored = match &document_idfs {
+                Some(idfs) => self.score_with_idfs(sentences, idfs, offset),
+                None => self.score_split(sentences, offset),
+            };
+            offset += len;
+            summary.extend(self.summarize_deduped(scored, per));
+        }
+        summary
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// penalizing sentences which are redundant with each other.
+    ///
+    /// Sentences are selected one at a time by Maximal Marginal Relevance,
+    /// i.e. each next sentence maximizes `lambda * relevance - (1 - lambda) * redundancy`,
+    /// where relevance is the similarity to the "core" sentence,
+    /// and redundancy is the greatest similarity to any already selected sentence.
+    /// The "core" sentence is selected first if [always included](SummarizerBuilder::always_include_core),
+    /// and [duplicates](SummarizerBuilder::dedup_threshold) are dropped,
+    /// so with `lambda == 1.0` this is equivalent to [`Summarizer::summarize_sentences`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided lambda is not in `0.0..=1.0`.
+    #[must_use]
+    pub fn summarize_sentences_mmr<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        lambda: f64,
+    ) -> Vec<&'a str> {
+        assert!((0.0..=1.0).contains(&lambda));
+        let Some(Scored {
+            sentences,
+            tf_idfs,
+            mut candidates,
+            core,
+            core_first,
+            scores,
+            ..
+        }) = self.score(text)
+        else {
+            return Vec::new();
+        };
+
+        let n = saturating_usize(n).min(candidates.len());
+        let mut redundancy = vec![0.0; sentences.len()];
+        let mut indices = Vec::with_capacity(n);
+        // Like the ranking, each duplicate takes up one of the `n` selections, but is dropped.
+        for selected in 0..n {
+            let k = if core_first && selected == 0 {
+                candidates.iter().position(|&i| i == core).unwrap()
+            } else {
+                let (k, _) = candidates
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(_, &i)| {
+                        let relevance = lambda * scores[i];
+                        let score = OrdFloat::new(relevance - (1.0 - lambda) * redundancy[i]);
+                        (score, Reverse(i))
+                    })
+                    .unwrap();
+                k
+            };
+            let i = candidates.swap_remove(k);
+            if duplicate_of(&sentences, &tf_idfs, &indices, i, self.dedup_threshold).is_some() {
+                continue;
+            }
+            indices.push(i);
+            for &j in &candidates {
+                let similarity = cosine_compare(&tf_idfs[i], &tf_idfs[j]);
+                redundancy[j] = f64::max(redundancy[j], similarity);
+            }
+        }
+        summarize_impl(sentences, indices, self.output_order)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// which covers as much of the text's weighted terms as possible.
+    ///
+    /// Sentences are selected one at a time, each next sentence adding
+    /// the most tf-idf weight in the whole text of the terms not yet covered by the summary,
+    /// with ties broken by relevance, as in [`Summarizer::summarize_sentences`].
+    /// This favors informative summaries of texts which cover many topics,
+    /// over summaries of the sentences closest to the "core" sentence.
+    /// See also [`Summarizer::summarize_with_coverage`].
+    #[must_use]
+    pub fn summarize_coverage<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        let Some(Scored {
+            sentences,
+            tf_idfs,
+            overall,
+            mut candidates,
+            scores,
+            ..
+        }) = self.score(text)
+        else {
+            return Vec::new();
+        };
+
+        let n = saturating_usize(n).min(candidates.len());
+        let mut covered = HashSet::default();
+        let mut indices = Vec::with_capacity(n);
+        while indices.len() < n {
+            let (k, i) = candidates
+                .iter()
+                .copied()
+                .enumerate()
+                .max_by_key(|&(_, i)| {
+                    let gain: f64 = tf_idfs[i]
+                        .keys()
+                        .filter(|term| !covered.contains(term))
+                        .map(|term| overall[term])
+                        .sum();
+                    (OrdFloat::new(gain), OrdFloat::new(scores[i]), Reverse(i))
+                })
+                .unwrap();
+            candidates.swap_remove(k);
+            indices.push(i);
+            covered.extend(tf_idfs[i].keys());
+        }
+        dedup(&sentences, &tf_idfs, &mut indices, self.dedup_threshold);
+        summarize_impl(sentences, indices, self.output_order)
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// ranked by TextRank instead of similarity to a "core" sentence.
+    ///
+    /// Sentences form a graph weighted by their pairwise similarity,
+    /// which is ranked by PageRank with the given damping factor.
+    /// Iteration stops once the ranks converge,
+    /// or after the given maximum number of iterations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided damping factor is not in `0.0..=1.0`.
+    #[must_use]
+    pub fn summarize_textrank<'a>(
+        &self,
+        text: &'a str,
+        n: NonZeroU32,
+        damping: f64,
+        iterations: usize,
+    ) -> Vec<&'a str> {
+        const TOLERANCE: f64 = 1e-6;
+
+        assert!((0.0..=1.0).contains(&damping));
+        let Some(scored) = self.score(text) else {
+            return Vec::new();
+        };
+
+        let len = scored.sentences.len();
+        let weights = similarity_matrix(&scored.tf_idfs);
+        let out: Vec<f64> = weights.iter().map(|row| row.iter().sum()).collect();
+        let mut ranks = vec![1.0 / len as f64; len];
+        for _ in 0..iterations {
+            // Sentences similar to no others distribute their rank evenly.
+            let dangling = (0..len)
+                .filter(|&j| out[j] == 0.0)
+                .map(|j| ranks[j])
+                .sum::<f64>()
+                / len as f64;
+            let next: Vec<f64> = (0..len)
+                .map(|i| {
+                    let inflow = (0..len)
+                        .filter(|&j| out[j] > 0.0)
+                        .map(|j| weights[j][i] / out[j] * ranks[j])
+                        .sum::<f64>();
+                    (1.0 - damping) / len as f64 + damping * (inflow + dangling)
+                })
+                .collect();
+            let delta: f64 = next.iter().zip(&ranks).map(|(x, y)| abs(x - y)).sum();
+            ranks = next;
+            if delta < TOLERANCE {
+                break;
+            }
+        }
+
+        let mut ranking = scored.candidates.clone();
+        sort_by_score(&mut ranking, &ranks);
+        let (indices, _) = scored.select(ranking, n, self.dedup_threshold);
+        summarize_impl(scored.sentences, indices, self.output_order)
+    }
+
+    /// The sentences of the text, and the matrix of their pairwise similarities,
+    /// e.g. to render the structure of the text as a heatmap.
+    ///
+    /// The similarity of the `i`th and `j`th sentences is at `matrix[i][j]`,
+    /// which is the cosine similarity of their tf-idf vectors, even with a
+    /// [custom similarity function](Summarizer::with_similarity).
+    /// So the matrix is symmetric, and each sentence's similarity to itself
+    /// is 1.0, or 0.0 if it has no weighted terms.
+    #[must_use]
+    pub fn similarity_matrix<'a>(&self, text: &'a str) -> (Vec<&'a str>, Vec<Vec<f64>>) {
+        let sentences = self.splitter.split(text);
+        let terms = self.analyzer.terms(&sentences);
+        let idfs = idfs(&terms, self.smooth_idf);
+        let mut tf_idfs = Vec::new();
+        tf_idfs_into(&terms, &idfs, self.weighting(&terms), &mut tf_idfs);
+        let mut matrix = similarity_matrix(&tf_idfs);
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = cosine_compare(&tf_idfs[i], &tf_idfs[i]);
+        }
+        (sentences, matrix)
+    }
+
+    /// Provide a `n` sentence summary for a Markdown document.
+    ///
+    /// Formatting such as headers, emphasis markers, and link URLs
+    /// is stripped before summarizing, so that it does not skew the term statistics.
+    /// Code blocks and raw HTML are ignored entirely.
+    /// Each returned sentence is the slice of the Markdown it was extracted from,
+    /// so it may still contain inline formatting.
+    #[cfg(feature = "markdown")]
+    #[must_use]
+    pub fn summarize_markdown<'a>(&self, md: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        let (plain, segments) = markdown_text(md);
+        self.summarize_sentences(&plain, n)
+            .into_iter()
+            .filter_map(|sentence| {
+                let sentence = sentence.trim();
+                if sentence.is_empty() {
+                    return None;
+                }
+                let range = source_range(&segments, sentence_range(&plain, sentence));
+                Some(&md[range])
+            })
+            .collect()
+    }
+
+    /// Provide a `n` sentence summary for an HTML document.
+    ///
+    /// Only the visible text of the document is summarized,
+    /// i.e. tags and attributes are stripped, and scripts and styles are ignored entirely.
+    /// Block elements such as paragraphs and headers always end a sentence,
+    /// and whitespace within the text is collapsed.
+    /// The sentences are returned as plain text, with surrounding whitespace trimmed.
+    #[cfg(feature = "html")]
+    #[must_use]
+    pub fn summarize_html(&self, html: &str, n: NonZeroU32) -> Vec<String> {
+        let text = html_text(html);
+        self.summarize_sentences(&text, n)
+            .into_iter()
+            .map(|sentence| sentence.trim().into())
+            .collect()
+    }
+
+    /// Provide a `n` sentence summary for a text of raw bytes.
+    ///
+    /// The text is decoded as UTF-8, or as UTF-16 if it starts with a UTF-16 byte order mark,
+    /// and any byte order mark is removed.
+    /// The sentences are those [`Summarizer::summarize_sentences`] returns for the decoded text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid in its encoding,
+    /// e.g. if it is neither UTF-8 nor has a UTF-16 byte order mark.
+    pub fn summarize_bytes(
+        &self,
+        bytes: &[u8],
+        n: NonZeroU32,
+    ) -> Result<Vec<String>, SummarizeError> {
+        let text = decode(bytes).ok_or(SummarizeError::InvalidEncoding)?;
+        Ok(self
+            .summarize_sentences(&text, n)
+            .into_iter()
+            .map(String::from)
+            .collect())
+    }
+
+    /// Provide a `n` sentence summary for the text, as a single string.
+    ///
+    /// Trailing whitespace is trimmed from each sentence,
+    /// or all whitespace is [normalized](SummarizerBuilder::normalize_whitespace) if enabled,
+    /// and the sentences are separated by `joiner`.
+    #[must_use]
+    pub fn summarize_to_string(&self, text: &str, n: NonZeroU32, joiner: &str) -> String {
+        let mut summary = String::new();
+        self.summarize_into(text, n, &mut summary, joiner);
+        summary
+    }
+
+    /// Provide a `n` sentence summary for the text, appended to `out`.
+    ///
+    /// This is like [`Summarizer::summarize_to_string`],
+    /// but reuses the caller's buffer instead of allocating a new one.
+    /// The existing contents of `out` are left intact,
+    /// and `joiner` is only inserted between the sentences of the summary.
+    pub fn summarize_into(&self, text: &str, n: NonZeroU32, out: &mut String, joiner: &str) {
+        for (i, sentence) in self.summarize_sentences_iter(text, n).enumerate() {
+            if i > 0 {
+                out.push_str(joiner);
+            }
+            if self.normalize_whitespace {
+                out.push_str(&collapse_whitespace(sentence));
+            } else {
+                out.push_str(sentence.trim_end());
+            }
+        }
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// with the whitespace surrounding each sentence left intact.
+    ///
+    /// Each sentence includes all whitespace up to the start of the next sentence,
+    /// and the first sentence of the text includes any leading whitespace,
+    /// so that concatenating adjacent sentences reproduces the original text exactly.
+    /// This only differs from [`Summarizer::summarize_sentences`]
+    /// if a custom [`SentenceSplitter`] omits whitespace from its sentences.
+    #[must_use]
+    pub fn summarize_sentences_verbatim<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<&'a str> {
+        self.summarize_sentences(text, n)
+            .into_iter()
+            .map(|sentence| {
+                let Range { mut start, mut end } = sentence_range(text, sentence);
+                if text[..start].trim_start().is_empty() {
+                    start = 0;
+                }
+                end = text.len() - text[end..].trim_start().len();
+                &text[start..end]
+            })
+            .collect()
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// as byte ranges into the text.
+    ///
+    /// The ranges are non-overlapping,
+    /// and slicing the text with them produces exactly
+    /// the sentences returned by [`Summarizer::summarize_sentences`], in the same order.
+    /// So the ranges are sorted, unless [ordered by relevance](SummarizerBuilder::output_order).
+    #[must_use]
+    pub fn summarize_sentence_ranges(&self, text: &str, n: NonZeroU32) -> Vec<Range<usize>> {
+        self.summarize_sentences(text, n)
+            .into_iter()
+            .map(|sentence| sentence_range(text, sentence))
+            .collect()
+    }
+
+    /// Provide a `n` sentence summary for the text,
+    /// with each sentence paired with its relevance score.
+    ///
+    /// The score is the final score by which the sentence is ranked:
+    /// its similarity to the "core" sentence, or to the document as a whole
+    /// depending on the [centrality](SummarizerBuilder::centrality),
+    /// after all configured adjustments such as [length normalization](SummarizerBuilder::length_normalization),
+    /// the [numeric boost](SummarizerBuilder::numeric_boost)
+    /// and the [position boost](SummarizerBuilder::position_boost).
+    /// So while the similarity is typically in `0.0..=1.0`, the score is unbounded,
+    /// e.g. a numeric boost of 10.0 can score a sentence 10.0.
+    /// Sentences are returned in the [output order](SummarizerBuilder::output_order),
+    /// i.e. by default in document order, not score order.
+    #[must_use]
+    pub fn summarize_scored<'a>(&self, text: &'a str, n: NonZeroU32) -> Vec<(&'a str, f64)> {
+        let Some(scored) = self.score(text) else {
+            return Vec::new();
+        };
+        let mut indices = self.select(&scored, n);
+        if self.output_order == OutputOrder::Document {
+            indices.sort_unstable();
+        }
+        indices
+            .into_iter()
+            .map(|i| (scored.sentences[i], scored.scores[i]))
+            .collect()
+    }
+
+    /// Rank every sentence of the text which may be selected for a summary,
+    /// with each sentence paired with its relevance score.
+    ///
+    /// The scores are those of [`Summarizer::summarize_scored`],
+    /// and sentences are returned in ranking order, i.e. by descending score,
+    /// so that the first `n` sentences are the candidates for an `n` sentence summary.
+    /// Sentences which are [too short](SummarizerBuilder::min_sentence_words),
+    /// have too many stop words, or have no words other than stop words and numbers
+    /// are omitted, since they are never selected.
+    #[must_use]
+    pub fn summarize_ranked<'a>(&self, text: &'a str) -> Vec<(&'a str, f64)> {
+        let (sentences, indices, scores) = self.summarize_indices(text);
+        indices
+            .into_iter()
+            .map(|i| (sentences[i], scores[i]))
+            .collect()
+    }
+
+    /// Score every sentence of the text, in document order,
+    /// e.g. to provide the scores as features for an external reranker.
+    ///
+    /// Returns an empty vector if no sentence may be selected for a summary,
+    /// since then there is no "core" sentence to compare with.
+    #[must_use]
+    pub fn score_sentences<'a>(&self, text: &'a str) -> Vec<SentenceScore<'a>> {
+        let Some(scored) = self.score(text) else {
+            return Vec::new();
+        };
+        let mut candidate = vec![false; scored.sentences.len()];
+        for &i in &scored.candidates {
+            candidate[i] = true;
+        }
+        let core = &scored.tf_idfs[scored.core];
+        scored
+            .sentences
+            .iter()
+            .zip(&scored.tf_idfs)
+            .enumerate()
+            .map(|(index, (&sentence, tf_idf))| SentenceScore {
+                sentence,
+                index,
+                candidate: candidate[index],
+                core_similarity: self.similarity(core, tf_idf),
+                document_similarity: self.similarity(&scored.overall, tf_idf),
+                score: scored.scores[index],
+            })
+            .collect()
+    }
+}
+
+/// The scores of a sentence, from [`Summarizer::score_sentences`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SentenceScore<'a> {
+    /// The sentence, as a slice of the text.
+    pub sentence: &'a str,
+    /// The zero-based index of the sentence among the text's sentences.
+    pub index: usize,
+    /// Whether the sentence may be selected for a summary.
+    pub candidate: bool,
+    /// The similarity of the sentence to the "core" sentence.
+    pub core_similarity: f64,
+    /// The similarity of the sentence to the text as a whole.
+    pub document_similarity: f64,
+    /// The relevance score by which the sentence is ranked,
+    /// as in [`Summarizer::summarize_scored`].
+    pub score: f64,
+}
+
+/// Builder for configuring a [`Summarizer`].
+///
+/// By default the summarizer is language agnostic,
+/// i.e. it neither stems words nor filters stop words.
+#[derive(Clone)]
+pub struct SummarizerBuilder {
+    #[cfg(feature = "std")]
+    language: Option<Language>,
+    #[cfg(feature = "std")]
+    stemmer: bool,
+    #[cfg(feature = "std")]
+    merge_abbreviations: bool,
+    treat_newlines_as_breaks: bool,
+    stop_words: Option<Vec<String>>,
+    case_sensitive: bool,
+    #[cfg(feature = "unicode-normalization")]
+    normalize_unicode: bool,
+    tokenizer: Arc<dyn Tokenizer>,
+    custom_stemmer: Option<Arc<dyn Stem>>,
+    ngram: usize,
+    scoring: ScoringScheme,
+    sublinear_tf: bool,
+    smooth_idf: bool,
+    length_normalization: LengthNormalization,
+    centrality: CentralityMode,
+    numeric_boost: f64,
+    position_boost: Option<PositionBoost>,
+    skip_leading: usize,
+    min_sentence_words: usize,
+    max_stop_word_ratio: f64,
+    dedup_threshold: f64,
+    always_include_core: bool,
+    output_order: OutputOrder,
+    normalize_whitespace: bool,
+}
+
+impl SummarizerBuilder {
+    /// Create a new `SummarizerBuilder`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            language: None,
+            #[cfg(feature = "std")]
+            stemmer: true,
+            #[cfg(feature = "std")]
+            merge_abbreviations: false,
+            treat_newlines_as_breaks: false,
+            stop_words: None,
+            case_sensitive: false,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode: false,
+            tokenizer: Arc::new(UnicodeWords),
+            custom_stemmer: None,
+            ngram: 1,
+            scoring: ScoringScheme::TfIdf,
+            sublinear_tf: false,
+            smooth_idf: false,
+            length_normalization: LengthNormalization::None,
+            centrality: CentralityMode::CoreSentence,
+            numeric_boost: 1.0,
+            position_boost: None,
+            skip_leading: 0,
+            min_sentence_words: 0,
+            max_stop_word_ratio: 1.0,
+            dedup_threshold: 1.0,
+            always_include_core: false,
+            output_order: OutputOrder::Document,
+            normalize_whitespace: false,
+        }
+    }
+
+    /// Set the document's language,
+    /// which selects the stemmer and the default stop words.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Enable or disable stemming (enabled by default).
+    ///
+    /// Stemming only takes effect if a language with a stemmer has been set.
+    /// Disabling it still filters the language's stop words,
+    /// e.g. to keep product names from being mangled by the stemmer.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn stemmer(mut self, enabled: bool) -> Self {
+        self.stemmer = enabled;
+        self
+    }
+
+    /// Enable or disable merging sentences split after abbreviations (disabled by default).
+    ///
+    /// The Unicode sentence boundary rules end a sentence after e.g. "Dr." in "Dr. Smith",
+    /// so when enabled, a sentence which ends in one of the language's common abbreviations
+    /// is merged with the sentence following it.
+    /// This only takes effect for English and German, and otherwise does nothing.
+    /// Note that a sentence which genuinely ends in an abbreviation is also merged.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn merge_abbreviations(mut self, enabled: bool) -> Self {
+        self.merge_abbreviations = enabled;
+        self
+    }
+
+    /// Enable or disable ending every sentence at a line break (disabled by default).
+    ///
+    /// When enabled, each line is split into sentences on its own.
+    /// The Unicode sentence boundary rules already end a sentence at every line break,
+    /// e.g. the items of a list without terminal punctuation are separate sentences either way,
+    /// so this only makes a difference when [merging abbreviations](SummarizerBuilder::merge_abbreviations):
+    /// a line which ends in an abbreviation, e.g. a list item ending in "etc.",
+    /// is then not merged with the line following it.
+    /// Note that a [custom sentence splitter](Summarizer::with_splitter) replaces this behavior.
+    #[must_use]
+    pub fn treat_newlines_as_breaks(mut self, enabled: bool) -> Self {
+        self.treat_newlines_as_breaks = enabled;
+        self
+    }
+
+    /// Use a custom set of stop words,
+    /// instead of the default stop words for the language.
+    ///
+    /// Stop words are matched case-insensitively, unless case sensitive.
+    #[must_use]
+    pub fn stop_words(mut self, stop_words: impl IntoIterator<Item = String>) -> Self {
+        self.stop_words = Some(stop_words.into_iter().collect());
+        self
+    }
+
+    /// Enable or disable case sensitivity (disabled by default).
+    ///
+    /// When enabled, words are not lowercased, so e.g. "US" and "us" are distinct terms,
+    /// and a word is only a stop word if it matches a stop word exactly.
+    #[must_use]
+    pub fn case_sensitive(mut self, enabled: bool) -> Self {
+        self.case_sensitive = enabled;
+        self
+    }
+
+    /// Enable or disable Unicode normalization (disabled by default).
+    ///
+    /// When enabled, words and stop words are converted to Normalization Form C,
+    /// so e.g. a precomposed "é" and an "e" followed by a combining acute accent
+    /// are the same term.
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalize_unicode(mut self, enabled: bool) -> Self {
+        self.normalize_unicode = enabled;
+        self
+    }
+
+    /// Use a custom tokenizer,
+    /// instead of splitting sentences into words by the Unicode word boundary rules.
+    #[must_use]
+    pub fn tokenizer(mut self, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.tokenizer = Arc::new(tokenizer);
+        self
+    }
+
+    /// Use a custom stemmer, e.g. a lemmatizer,
+    /// instead of the stemmer of the document's language.
+    ///
+    /// The custom stemmer is used regardless of the language,
+    /// and words are still lowercased after stemming unless case sensitive.
+    #[must_use]
+    pub fn custom_stemmer(mut self, stemmer: impl Stem + 'static) -> Self {
+        self.custom_stemmer = Some(Arc::new(stemmer));
+        self
+    }
+
+    /// Set the maximum number of consecutive words in a term (1 by default).
+    ///
+    /// In addition to single words, each run of up to `n` consecutive words
+    /// is also a term, e.g. "machine learning" with `n == 2`.
+    /// Runs never span stop words. An `n` of 0 is treated as 1.
+    #[must_use]
+    pub fn ngram(mut self, n: usize) -> Self {
+        self.ngram = n.max(1);
+        self
+    }
+
+    /// Set how terms are weighted when scoring sentences (tf-idf by default).
+    #[must_use]
+    pub fn scoring(mut self, scoring: ScoringScheme) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Enable or disable sublinear term frequency scaling (disabled by default).
+    ///
+    /// When enabled, a term's frequency `tf` within a sentence is replaced by `1 + log2(tf)`,
+    /// so that repeating a term has diminishing returns.
+    #[must_use]
+    pub fn sublinear_tf(mut self, enabled: bool) -> Self {
+        self.sublinear_tf = enabled;
+        self
+    }
+
+    /// Enable or disable smoothing of inverse document frequencies (disabled by default).
+    ///
+    /// By default, the idf of a term in `df` of `n` sentences is `log2(n / df)`,
+    /// which is 0 for a term in every sentence, so that such terms are ignored.
+    /// When enabled, it's `log2((n + 1) / (df + 1)) + 1` instead, as if there were
+    /// an extra sentence containing every term, plus one so that no term is ignored.
+    /// This also dampens the weight of rare terms in short documents.
+    #[must_use]
+    pub fn smooth_idf(mut self, enabled: bool) -> Self {
+        self.smooth_idf = enabled;
+        self
+    }
+
+    /// Set how sentences' scores are normalized by their length (not at all by default).
+    ///
+    /// This prevents long sentences from dominating summaries
+    /// just because they contain more terms.
+    #[must_use]
+    pub fn length_normalization(mut self, normalization: LengthNormalization) -> Self {
+        self.length_normalization = normalization;
+        self
+    }
+
+    /// Set what sentences are ranked by their similarity to (the "core" sentence by default).
+    #[must_use]
+    pub fn centrality(mut self, centrality: CentralityMode) -> Self {
+        self.centrality = centrality;
+        self
+    }
+
+    /// Multiply the scores of sentences which contain numbers by this factor (1.0 by default).
+    ///
+    /// A sentence contains a number if any of its words contain a digit,
+    /// e.g. "12%" or "$4.2B". This is useful for e.g. financial reports,
+    /// where the sentences with figures are typically the most important.
+    #[must_use]
+    pub fn numeric_boost(mut self, multiplier: f64) -> Self {
+        self.numeric_boost = multiplier;
+        self
+    }
+
+    /// Boost the scores of sentences near the start and end of the document
+    /// (not at all by default).
+    ///
+    /// This is useful for e.g. scientific papers,
+    /// where the introduction and conclusion typically summarize the paper.
+    /// The boost follows a U-shaped curve over the document:
+    /// the scores of the first and last sentences are multiplied by `1 + magnitude`,
+    /// and the multiplier falls linearly to 1.0 at a distance of `fraction`
+    /// of the document from either end, e.g. 0.1 for the first and last 10%.
+    /// Sentences further from the ends are unaffected.
+    #[must_use]
+    pub fn position_boost(mut self, fraction: f64, magnitude: f64) -> Self {
+        self.position_boost = Some(PositionBoost {
+            fraction,
+            magnitude,
+        });
+        self
+    }
+
+    /// Never select the first `n` sentences of the document (0 by default).
+    ///
+    /// This is useful for e.g. scraped articles,
+    /// where the first sentences are often a byline or dateline rather than content.
+    /// The skipped sentences still contribute to the document's term statistics,
+    /// and the indices of sentences, e.g. from [`Summarizer::summarize_sentences_indexed`],
+    /// are still their positions in the whole document.
+    #[must_use]
+    pub fn skip_leading(mut self, n: usize) -> Self {
+        self.skip_leading = n;
+        self
+    }
+
+    /// Never select sentences with fewer than this many words (0 by default).
+    ///
+    /// This is useful for excluding short fragments such as "Yes." from summaries.
+    /// Words are counted by the [tokenizer](SummarizerBuilder::tokenizer), including stop words.
+    #[must_use]
+    pub fn min_sentence_words(mut self, n: usize) -> Self {
+        self.min_sentence_words = n;
+        self
+    }
+
+    /// Never select sentences in which more than this fraction of words are stop words
+    /// (1.0 by default).
+    ///
+    /// This is useful for excluding sentences with little content, such as "It is what it is."
+    /// Stop words are still ignored when scoring sentences regardless.
+    #[must_use]
+    pub fn max_stop_word_ratio(mut self, ratio: f64) -> Self {
+        self.max_stop_word_ratio = ratio;
+        self
+    }
+
+    /// Set the similarity at or above which a selected sentence
+    /// is considered a duplicate of a more relevant selected sentence (1.0 by default).
+    ///
+    /// Duplicates are dropped from every summary, e.g. from [`Summarizer::summarize_sentences`],
+    /// so a summary of `n` sentences may have fewer than `n`,
+    /// unless they are [replaced](Summarizer::summarize_sentences_filled).
+    /// Summaries limited by a budget, e.g. [`Summarizer::summarize_ratio`],
+    /// instead skip duplicates without counting them towards the budget.
+    /// The default only drops sentences which are identical in their terms or their text,
+    /// and a threshold above 1.0 drops none at all.
+    #[must_use]
+    pub fn dedup_threshold(mut self, threshold: f64) -> Self {
+        self.dedup_threshold = threshold;
+        self
+    }
+
+    /// Always rank the "core" sentence first (false by default),
+    /// i.e. the candidate sentence most similar to the text at large.
+    ///
+    /// The core sentence is usually the most relevant sentence by construction,
+    /// but a [position boost](SummarizerBuilder::position_boost),
+    /// a [numeric boost](SummarizerBuilder::numeric_boost), length normalization
+    /// or a [blended centrality](CentralityMode::Blend) may rank other sentences above it,
+    /// so that it's left out of short summaries, or dropped as a duplicate.
+    /// When enabled, the core sentence is ranked first regardless of its score,
+    /// so every summary from [`Summarizer::summarize_sentences`] includes it.
+    ///
+    /// ```
+    /// # use std::num::NonZeroU32;
+    /// # use summary::SummarizerBuilder;
+    /// let text = "Rust is fast. Rust is a systems language that is fast and safe. Rust is safe.";
+    /// let summarizer = SummarizerBuilder::new()
+    ///     .position_boost(0.34, 10.0)
+    ///     .always_include_core(true)
+    ///     .build();
+    /// let n = NonZeroU32::new(1).unwrap();
+    /// let core = summarizer.core_sentence(text).unwrap();
+    /// assert_eq!(summarizer.summarize_sentences(text, n), [core]);
+    /// ```
+    #[must_use]
+    pub fn always_include_core(mut self, enabled: bool) -> Self {
+        self.always_include_core = enabled;
+        self
+    }
+
+    /// Set the order in which the sentences of a summary are returned (document order by default).
+    #[must_use]
+    pub fn output_order(mut self, order: OutputOrder) -> Self {
+        self.output_order = order;
+        self
+    }
+
+    /// Trim whitespace from both ends of each sentence of a summary,
+    /// and collapse runs of whitespace within it to single spaces (false by default).
+    ///
+    /// This cleans up e.g. line breaks and indentation from the source for display,
+    /// but as it may need to allocate, it only applies to the summaries from
+    /// [`Summarizer::summarize_to_string`], [`Summarizer::summarize_into`]
+    /// and [`Summarizer::summarize_sentences_cow`],
+    /// while the methods which borrow from the text return its sentences as they are.
+    ///
+    /// ```
+    /// # use std::num::NonZeroU32;
+    /// # use summary::SummarizerBuilder;
+    /// let summarizer = SummarizerBuilder::new().normalize_whitespace(true).build();
+    /// let text = "  An  indented\t sentence.  ";
+    /// let n = NonZeroU32::new(1).unwrap();
+    /// assert_eq!(summarizer.summarize_to_string(text, n, " "), "An indented sentence.");
+    /// ```
+    #[must_use]
+    pub fn normalize_whitespace(mut self, enabled: bool) -> Self {
+        self.normalize_whitespace = enabled;
+        self
+    }
+
+    /// The options set so far, except for a custom tokenizer or stemmer.
+    #[must_use]
+    pub fn config(&self) -> SummarizerConfig {
+        let Self {
+            #[cfg(feature = "std")]
+            language,
+            #[cfg(feature = "std")]
+            stemmer,
+            #[cfg(feature = "std")]
+            merge_abbreviations,
+            treat_newlines_as_breaks,
+            stop_words,
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode,
+            tokenizer: _,
+            custom_stemmer: _,
+            ngram,
+            scoring,
+            sublinear_tf,
+            smooth_idf,
+            length_normalization,
+            centrality,
+            numeric_boost,
+            position_boost,
+            skip_leading,
+            min_sentence_words,
+            max_stop_word_ratio,
+            dedup_threshold,
+            always_include_core,
+            output_order,
+            normalize_whitespace,
+        } = self;
+        SummarizerConfig {
+            #[cfg(feature = "std")]
+            language: *language,
+            #[cfg(feature = "std")]
+            stemmer: *stemmer,
+            #[cfg(feature = "std")]
+            merge_abbreviations: *merge_abbreviations,
+            treat_newlines_as_breaks: *treat_newlines_as_breaks,
+            stop_words: stop_words.clone(),
+            case_sensitive: *case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode: *normalize_unicode,
+            ngram: *ngram,
+            scoring: *scoring,
+            sublinear_tf: *sublinear_tf,
+            smooth_idf: *smooth_idf,
+            length_normalization: *length_normalization,
+            centrality: *centrality,
+            numeric_boost: *numeric_boost,
+            position_boost: *position_boost,
+            skip_leading: *skip_leading,
+            min_sentence_words: *min_sentence_words,
+            max_stop_word_ratio: *max_stop_word_ratio,
+            dedup_threshold: *dedup_threshold,
+            always_include_core: *always_include_core,
+            output_order: *output_order,
+            normalize_whitespace: *normalize_whitespace,
+        }
+    }
+
+    /// Build the `Summarizer`.
+    #[must_use]
+    pub fn build(self) -> Summarizer {
+        let Self {
+            #[cfg(feature = "std")]
+            language,
+            #[cfg(feature = "std")]
+            stemmer,
+            #[cfg(feature = "std")]
+            merge_abbreviations,
+            treat_newlines_as_breaks,
+            stop_words,
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode,
+            tokenizer,
+            custom_stemmer,
+            ngram,
+            scoring,
+            sublinear_tf,
+            smooth_idf,
+            length_normalization,
+            centrality,
+            numeric_boost,
+            position_boost,
+            skip_leading,
+            min_sentence_words,
+            max_stop_word_ratio,
+            dedup_threshold,
+            always_include_core,
+            output_order,
+            normalize_whitespace,
+        } = self;
+
+        #[cfg(feature = "std")]
+        let (algorithm, stop_words) = (
+            match language {
+                Some(language) if stemmer => Stemmer::algorithm(language),
+                _ => None,
+            },
+            stop_words.or_else(|| language.map(StopWords::for_language)),
+        );
+        #[cfg(not(feature = "std"))]
+        let algorithm = None;
+        let stemmer = Stemmer {
+            algorithm,
+            custom: custom_stemmer,
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize: normalize_unicode,
+        };
+        #[cfg(feature = "std")]
+        let splitter: Box<dyn SentenceSplitter> = match language
+            .filter(|_| merge_abbreviations)
+            .and_then(AbbreviationSentences::for_language)
+        {
+            Some(splitter) => Box::new(splitter),
+            None => Box::new(UnicodeSentences),
+        };
+        #[cfg(not(feature = "std"))]
+        let splitter: Box<dyn SentenceSplitter> = Box::new(UnicodeSentences);
+        let splitter = if treat_newlines_as_breaks {
+            Box::new(LineSentences(splitter))
+        } else {
+            splitter
+        };
+        let words = stop_words.unwrap_or_default();
+        let mut stop_words = StopWords {
+            words: HashSet::default(),
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize: normalize_unicode,
+        };
+        stop_words.extend(words);
+        Summarizer {
+            analyzer: Analyzer {
+                tokenizer,
+                stop_words,
+                stop_phrases: StopPhrases::default(),
+                stemmer,
+                ngram,
+            },
+            splitter,
+            similarity: None,
+            scoring,
+            sublinear_tf,
+            smooth_idf,
+            length_normalization,
+            centrality,
+            numeric_boost,
+            position_boost,
+            skip_leading,
+            min_sentence_words,
+            max_stop_word_ratio,
+            dedup_threshold,
+            always_include_core,
+            output_order,
+            normalize_whitespace,
+        }
+    }
+}
+
+impl Default for SummarizerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<SummarizerConfig> for SummarizerBuilder {
+    fn from(config: SummarizerConfig) -> Self {
+        let SummarizerConfig {
+            #[cfg(feature = "std")]
+            language,
+            #[cfg(feature = "std")]
+            stemmer,
+            #[cfg(feature = "std")]
+            merge_abbreviations,
+            treat_newlines_as_breaks,
+            stop_words,
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode,
+            ngram,
+            scoring,
+            sublinear_tf,
+            smooth_idf,
+            length_normalization,
+            centrality,
+            numeric_boost,
+            position_boost,
+            skip_leading,
+            min_sentence_words,
+            max_stop_word_ratio,
+            dedup_threshold,
+            always_include_core,
+            output_order,
+            normalize_whitespace,
+        } = config;
+        Self {
+            #[cfg(feature = "std")]
+            language,
+            #[cfg(feature = "std")]
+            stemmer,
+            #[cfg(feature = "std")]
+            merge_abbreviations,
+            treat_newlines_as_breaks,
+            stop_words,
+            case_sensitive,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_unicode,
+            tokenizer: Arc::new(UnicodeWords),
+            custom_stemmer: None,
+            ngram: ngram.max(1),
+            scoring,
+            sublinear_tf,
+            smooth_idf,
+            length_normalization,
+            centrality,
+            numeric_boost,
+            position_boost,
+            skip_leading,
+            min_sentence_words,
+            max_stop_word_ratio,
+            dedup_threshold,
+            always_include_core,
+            output_order,
+            normalize_whitespace,
+        }
+    }
+}
+
+/// The options of a [`SummarizerBuilder`], e.g. for persisting them alongside a corpus.
+///
+/// With the `serde` feature, the configuration can be serialized and deserialized,
+/// and any options missing when deserializing take their default values.
+/// A configuration is converted to and from a builder with
+/// [`SummarizerBuilder::config`] and [`SummarizerBuilder::from`],
+/// so building the same configuration always produces an equivalent [`Summarizer`].
+///
+/// A custom [`Tokenizer`] or [`Stem`] is not part of the configuration,
+/// and the defaults are used when converting back to a builder.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct SummarizerConfig {
+    /// See [`SummarizerBuilder::language`].
+    #[cfg(feature = "std")]
+    pub language: Option<Language>,
+    /// See [`SummarizerBuilder::stemmer`].
+    #[cfg(feature = "std")]
+    pub stemmer: bool,
+    /// See [`SummarizerBuilder::merge_abbreviations`].
+    #[cfg(feature = "std")]
+    pub merge_abbreviations: bool,
+    /// See [`SummarizerBuilder::treat_newlines_as_breaks`].
+    pub treat_newlines_as_breaks: bool,
+    /// See [`SummarizerBuilder::stop_words`],
+    /// or `None` for the default stop words of the language.
+    pub stop_words: Option<Vec<String>>,
+    /// See [`SummarizerBuilder::case_sensitive`].
+    pub case_sensitive: bool,
+    /// See [`SummarizerBuilder::normalize_unicode`].
+    #[cfg(feature = "unicode-normalization")]
+    pub normalize_unicode: bool,
+    /// See [`SummarizerBuilder::ngram`].
+    pub ngram: usize,
+    /// See [`SummarizerBuilder::scoring`].
+    pub scoring: ScoringScheme,
+    /// See [`SummarizerBuilder::sublinear_tf`].
+    pub sublinear_tf: bool,
+    /// See [`SummarizerBuilder::smooth_idf`].
+    pub smooth_idf: bool,
+    /// See [`SummarizerBuilder::length_normalization`].
+    pub length_normalization: LengthNormalization,
+    /// See [`SummarizerBuilder::centrality`].
+    pub centrality: CentralityMode,
+    /// See [`SummarizerBuilder::numeric_boost`].
+    pub numeric_boost: f64,
+    /// See [`SummarizerBuilder::position_boost`], or `None` for no boost.
+    pub position_boost: Option<PositionBoost>,
+    /// See [`SummarizerBuilder::skip_leading`].
+    pub skip_leading: usize,
+    /// See [`SummarizerBuilder::min_sentence_words`].
+    pub min_sentence_words: usize,
+    /// See [`SummarizerBuilder::max_stop_word_ratio`].
+    pub max_stop_word_ratio: f64,
+    /// See [`SummarizerBuilder::dedup_threshold`].
+    pub dedup_threshold: f64,
+    /// See [`SummarizerBuilder::always_include_core`].
+    pub always_include_core: bool,
+    /// See [`SummarizerBuilder::output_order`].
+    pub output_order: OutputOrder,
+    /// See [`SummarizerBuilder::normalize_whitespace`].
+    pub normalize_whitespace: bool,
+}
+
+impl Default for SummarizerConfig {
+    fn default() -> Self {
+        SummarizerBuilder::new().config()
+    }
+}
+
+/// Summarizer for documents which are too large to hold in memory at once.
+///
+/// The document is pushed in chunks of any size, e.g. lines of a file,
+/// and is split into sentences as they are completed.
+/// The term statistics of the whole document are kept exactly,
+/// which takes memory proportional to the document's vocabulary,
+/// but only a bounded number of candidate sentences are retained.
+///
+/// # Accuracy
+///
+/// Whenever `2 * capacity` candidates have been retained,
+/// they are ranked by the statistics of the document so far, and only the `capacity`
+/// most relevant are kept, with the "core" sentence chosen among the retained sentences.
+/// So a sentence which is evicted early, before the statistics have settled,
+/// can not be selected even if it would have been relevant to the whole document.
+/// The larger the capacity, the closer the summary is to that of
+/// [`Summarizer::summarize_sentences`], and if the document has at most
+/// `2 * capacity` sentences, the summary is the same.
+pub struct StreamingSummarizer<'a> {
+    summarizer: &'a Summarizer,
+    capacity: usize,
+    /// The text of the sentence which may be continued by the next chunk.
+    pending: String,
+    sentences: usize,
+    terms: usize,
+    /// The number of sentences each term appears in.
+    document_frequencies: IdfMap,
+    /// The frequencies of the terms in the whole document.
+    term_frequencies: IdfMap,
+    retained: Vec<Retained>,
+}
+
+/// A candidate sentence retained by a [`StreamingSummarizer`].
+struct Retained {
+    index: usize,
+    text: String,
+    term_frequencies: IdfMap,
+}
+
+impl<'a> StreamingSummarizer<'a> {
+    /// Create a new `StreamingSummarizer`, which retains
+    /// between `capacity` and `2 * capacity` of the most relevant candidate sentences.
+    ///
+    /// The capacity should be at least the number of sentences in the summary.
+    #[must_use]
+    pub fn new(summarizer: &'a Summarizer, capacity: NonZeroUsize) -> Self {
+        Self {
+            summarizer,
+            capacity: capacity.get(),
+            pending: String::new(),
+            sentences: 0,
+            terms: 0,
+            document_frequencies: IdfMap::default(),
+            term_frequencies: IdfMap::default(),
+            retained: Vec::new(),
+        }
+    }
+
+    /// Push the next chunk of the document.
+    pub fn push(&mut self, chunk: &str) {
+        self.pending.push_str(chunk);
+        let pending = core::mem::take(&mut self.pending);
+        let sentences = self.summarizer.splitter.split(&pending);
+        // The last sentence may be continued by the next chunk.
+        let Some((last, complete)) = sentences.split_last() else {
+            // e.g. an opening quote, which is kept for the sentence it begins.
+            self.pending = pending;
+            return;
+        };
+        for sentence in complete {
+            self.push_sentence(sentence);
+        }
+        let start = sentence_range(&pending, last).start;
+        self.pending = pending;
+        self.pending.drain(..start);
+    }
+
+    /// Provide a `n` sentence summary for the document pushed so far.
+    #[must_use]
+    pub fn finish(mut self, n: NonZeroU32) -> Vec<String> {
+        let pending = core::mem::take(&mut self.pending);
+        for sentence in self.summarizer.splitter.split(&pending) {
+            self.push_sentence(sentence);
+        }
+
+        let tf_idfs = self.rank();
+        let mut indices: Vec<_> = (0..self.retained.len()).collect();
+        indices.truncate(saturating_usize(n));
+        let sentences: Vec<_> = self
+            .retained
+            .iter()
+            .map(|retained| &*retained.text)
+            .collect();
+        dedup(
+            &sentences,
+            &tf_idfs,
+            &mut indices,
+            self.summarizer.dedup_threshold,
+        );
+        if self.summarizer.output_order == OutputOrder::Document {
+            indices.sort_unstable_by_key(|&i| self.retained[i].index);
+        }
+
+        let mut retained: Vec<_> = self.retained.into_iter().map(Some).collect();
+        indices
+            .into_iter()
+            .map(|i| retained[i].take().unwrap().text)
+            .collect()
+    }
+
+    fn push_sentence(&mut self, sentence: &str) {
+        let mut term_frequencies = IdfMap::default();
+        let terms = self.summarizer.analyzer.terms(&[sentence]);
+        term_frequencies_into(&terms, &mut term_frequencies);
+        let is_candidate = self
+            .summarizer
+            .is_candidate(self.sentences, sentence, &terms[0]);
+
+        self.sentences += 1;
+        for (term, tf) in &term_frequencies {
+            self.terms += *tf as usize;
+            *self.document_frequencies.entry_ref(&**term).or_default() += 1.0;
+            *self.term_frequencies.entry_ref(&**term).or_default() += tf;
+        }
+
+        if is_candidate {
+            self.retained.push(Retained {
+                index: self.sentences - 1,
+                text: sentence.into(),
+                term_frequencies,
+            });
+            if self.retained.len() >= 2 * self.capacity {
+                self.rank();
+                self.retained.truncate(self.capacity);
+            }
+        }
+    }
+
+    /// Sort the retained sentences by descending score,
+    /// and return their tf-idf vectors in the same order.
+    fn rank(&mut self) -> Vec<IdfMap> {
+        let Self {
+            summarizer,
+            sentences,
+            terms,
+            document_frequencies,
+            term_frequencies,
+            retained,
+            ..
+        } = self;
+
+        if retained.is_empty() {
+            return Vec::new();
+        }
+        let n = *sentences as f64;
+        let idfs: IdfMap = document_frequencies
+            .iter()
+            .map(|(term, df)| {
+                let idf = inverse_document_frequency(n, *df, summarizer.smooth_idf);
+                (term.clone(), idf)
+            })
+            .collect();
+        let weighting = summarizer.weighting_with(|| *terms as f64 / n);
+        let tf_idfs: Vec<_> = retained
+            .iter()
+            .map(|retained| {
+                let mut tf_idf = retained.term_frequencies.clone();
+                weigh(&mut tf_idf, &idfs, weighting);
+                tf_idf
+            })
+            .collect();
+        let mut overall = term_frequencies.clone();
+        weigh(&mut overall, &idfs, weighting);
+
+        let core = (0..retained.len())
+            .max_by_key(|&i| {
+                (
+                    OrdFloat::new(summarizer.similarity(&tf_idfs[i], &overall)),
+                    Reverse(retained[i].index),
+                )
+            })
+            .unwrap();
+        let scores: Vec<_> = (0..retained.len())
+            .map(|i| {
+                let relevance =
+                    summarizer.relevance(&tf_idfs[core], &overall, &tf_idfs[i], &retained[i].text);
+                relevance * summarizer.position_multiplier(retained[i].index, *sentences)
+            })
+            .collect();
+
+        let mut order: Vec<_> = (0..retained.len()).collect();
+        sort_by_score(&mut order, &scores);
+        if summarizer.always_include_core {
+            let position = order.iter().position(|&i| i == core).unwrap();
+            order[..=position].rotate_right(1);
+        }
+        let mut slots: Vec<_> = retained.drain(..).zip(tf_idfs).map(Some).collect();
+        order
+            .into_iter()
+            .map(|i| {
+                let (sentence, tf_idf) = slots[i].take().unwrap();
+                retained.push(sentence);
+                tf_idf
+            })
+            .collect()
+    }
+}
+
+/// Summarizer for documents which grow over time, and are summarized after each addition.
+///
+/// The document is appended in chunks of any size, and is split into sentences
+/// as they are completed. The terms of each complete sentence are counted only once,
+/// so summarizing again after an append only analyzes the new text,
+/// though the tf-idf vectors are still reweighted by the statistics of the whole document.
+///
+/// The summary is the same as that of [`Summarizer::summarize_sentences`]
+/// for the whole text appended so far.
+///
+/// ```
+/// # use std::num::NonZeroU32;
+/// # use summary::{AppendableSummarizer, SummarizerBuilder};
+/// let summarizer = SummarizerBuilder::new().build();
+/// let mut document = AppendableSummarizer::new(&summarizer);
+/// document.append("The cat sat on the mat. ");
+/// document.append("The dog sat on the log. The cat ");
+/// document.append("chased the dog.");
+/// let n = NonZeroU32::new(1).unwrap();
+/// assert_eq!(document.summarize(n), summarizer.summarize_sentences(document.text(), n));
+/// ```
+pub struct AppendableSummarizer<'a> {
+    summarizer: &'a Summarizer,
+    text: String,
+    /// The start of the sentence which may be continued by the next text.
+    pending: usize,
+    /// The complete sentences, in document order.
+    sentences: Vec<Appended>,
+    statistics: Statistics,
+}
+
+/// A complete sentence of an [`AppendableSummarizer`].
+struct Appended {
+    range: Range<usize>,
+    term_frequencies: IdfMap,
+    is_candidate: bool,
+}
+
+/// The term statistics of the sentences of a document.
+#[derive(Clone, Default)]
+struct Statistics {
+    terms: usize,
+    /// The number of sentences each term appears in.
+    document_frequencies: IdfMap,
+    /// The frequencies of the terms in the whole document.
+    term_frequencies: IdfMap,
+}
+
+impl Statistics {
+    fn add(&mut self, sentence: &Appended) {
+        for (term, tf) in &sentence.term_frequencies {
+            self.terms += *tf as usize;
+            *self.document_frequencies.entry_ref(&**term).or_default() += 1.0;
+            *self.term_frequencies.entry_ref(&**term).or_default() += tf;
+        }
+    }
+}
+
+impl<'a> AppendableSummarizer<'a> {
+    /// Create a new `AppendableSummarizer` for an empty document.
+    #[must_use]
+    pub fn new(summarizer: &'a Summarizer) -> Self {
+        Self {
+            summarizer,
+            text: String::new(),
+            pending: 0,
+            sentences: Vec::new(),
+            statistics: Statistics::default(),
+        }
+    }
+
+    /// The text appended so far.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Append the next text of the document.
+    pub fn append(&mut self, new_text: &str) {
+        self.text.push_str(new_text);
+        let ranges = self.pending_ranges();
+        // The last sentence may be continued by the next text.
+        let Some((last, complete)) = ranges.split_last() else {
+            return;
+        };
+        for range in complete {
+            let sentence = self.analyze(self.sentences.len(), range.clone());
+            self.statistics.add(&sentence);
+            self.sentences.push(sentence);
+        }
+        self.pending = last.start;
+    }
+
+    /// Provide a `n` sentence summary for the document appended so far.
+    #[must_use]
+    pub fn summarize(&self, n: NonZeroU32) -> Vec<&str> {
+        let pending: Vec<_> = self
+            .pending_ranges()
+            .into_iter()
+            .enumerate()
+            .map(|(i, range)| self.analyze(self.sentences.len() + i, range))
+            .collect();
+        let mut statistics = self.statistics.clone();
+        for sentence in &pending {
+            statistics.add(sentence);
+        }
+        let appended: Vec<_> = self.sentences.iter().chain(&pending).collect();
+        if appended.is_empty() {
+            return Vec::new();
+        }
+
+        let n_sentences = appended.len() as f64;
+        let idfs: IdfMap = statistics
+            .document_frequencies
+            .iter()
+            .map(|(term, df)| {
+                let idf = inverse_document_frequency(n_sentences, *df, self.summarizer.smooth_idf);
+                (term.clone(), idf)
+            })
+            .collect();
+        let weighting = self
+            .summarizer
+            .weighting_with(|| statistics.terms as f64 / n_sentences);
+        let tf_idfs = appended
+            .iter()
+            .map(|sentence| {
+                let mut tf_idf = sentence.term_frequencies.clone();
+                weigh(&mut tf_idf, &idfs, weighting);
+                tf_idf
+            })
+            .collect();
+        let mut overall = statistics.term_frequencies;
+        weigh(&mut overall, &idfs, weighting);
+        let candidates = (0..appended.len())
+            .filter(|&i| appended[i].is_candidate)
+            .collect();
+
+        let sentences = appended
+            .iter()
+            .map(|sentence| &self.text[sentence.range.clone()])
+            .collect();
+        let scored = self
+            .summarizer
+            .score_vectors(sentences, tf_idfs, overall, candidates);
+        self.summarizer.summarize_deduped(scored, n)
+    }
+
+    /// The ranges of the sentences from the start of the pending sentence.
+    fn pending_ranges(&self) -> Vec<Range<usize>> {
+        let text = &self.text[self.pending..];
+        self.summarizer
+            .splitter
+            .split(text)
+            .into_iter()
+            .map(|sentence| {
+                let range = sentence_range(text, sentence);
+                self.pending + range.start..self.pending + range.end
+            })
+            .collect()
+    }
+
+    /// Analyze the sentence at `position` in the document.
+    fn analyze(&self, position: usize, range: Range<usize>) -> Appended {
+        let sentence = &self.text[range.clone()];
+        let terms = self.summarizer.analyzer.terms(&[sentence]);
+        let mut term_frequencies = IdfMap::default();
+        term_frequencies_into(&terms, &mut term_frequencies);
+        Appended {
+            range,
+            term_frequencies,
+            is_candidate: self.summarizer.is_candidate(position, sentence, &terms[0]),
+        }
+    }
+}
+
+/// A custom similarity function, see [`Summarizer::with_similarity`].
+type Similarity = dyn Fn(TermVector<'_>, TermVector<'_>) -> f64 + Send + Sync;
+
+/// The tf-idf vector of a sentence or document,
+/// as passed to a [custom similarity function](Summarizer::with_similarity).
+///
+/// The vector is sparse, i.e. it only has weights for the terms which occur,
+/// and is normalized to a unit vector unless it has no weights at all.
+#[derive(Clone, Copy)]
+pub struct TermVector<'a>(&'a IdfMap);
+
+impl<'a> TermVector<'a> {
+    /// The weight of the (stemmed) term, or 0.0 if it does not occur.
+    #[must_use]
+    pub fn get(&self, term: &str) -> f64 {
+        self.0.get(term).copied().unwrap_or(0.0)
+    }
+
+    /// The terms which occur, with their weights, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, f64)> {
+        self.0.iter().map(|(term, &weight)| (&**term, weight))
+    }
+
+    /// The number of terms which occur.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no terms occur.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for TermVector<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Splits a document into sentences.
+pub trait SentenceSplitter: Send + Sync {
+    /// Split the text into sentences, in document order.
+    ///
+    /// The returned sentences must be slices of the text.
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str>;
+}
+
+/// Splits sentences by the Unicode sentence boundary rules.
+///
+/// This is the default sentence splitter.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnicodeSentences;
+
+impl SentenceSplitter for UnicodeSentences {
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        sentences(text)
+    }
+}
+
+/// Splits sentences by the Unicode sentence boundary rules,
+/// but merges sentences which were split after an abbreviation.
+#[cfg(feature = "std")]
+struct AbbreviationSentences {
+    /// Abbreviations, without their final period.
+    abbreviations: &'static [&'static str],
+}
+
+#[cfg(feature = "std")]
+impl AbbreviationSentences {
+    fn for_language(language: Language) -> Option<Self> {
+        #[rustfmt::skip]
+        let abbreviations: &[&str] = match language {
+            Language::English => &[
+                "Mr", "Mrs", "Ms", "Dr", "Prof", "Sr", "Jr", "St", "Mt", "Gen", "Gov", "Sen", "Rep",
+                "Inc", "Ltd", "Co", "Corp", "No", "Nos", "Fig", "Vol", "approx", "etc", "vs",
+                "e.g", "i.e", "a.m", "p.m", "U.S", "U.K", "U.N", "E.U",
+                "Jan", "Feb", "Mar", "Apr", "Jun", "Jul", "Aug", "Sep", "Sept", "Oct", "Nov", "Dec",
+            ],
+            Language::German => &[
+                "Dr", "Prof", "Hr", "Fr", "Nr", "Str", "St", "Jh", "Abs", "Abb", "Bd", "Tel",
+                "bzw", "ca", "vgl", "evtl", "ggf", "inkl", "sog", "usw", "etc", "zzgl",
+                "z.B", "d.h", "u.a", "o.Ä", "s.o", "s.u", "z.T", "u.U",
+                "Jan", "Feb", "Apr", "Aug", "Sept", "Okt", "Nov", "Dez",
+            ],
+            _ => {
+                return None;
+            }
+        };
+        Some(Self { abbreviations })
+    }
+
+    fn ends_with_abbreviation(&self, sentence: &str) -> bool {
+        let Some(sentence) = sentence.trim_end().strip_suffix('.') else {
+            return false;
+        };
+        let word = sentence
+            .rsplit(|c: char| !(c.is_alphanumeric() || c == '.'))
+            .next()
+            .unwrap_or_default();
+        self.abbreviations.contains(&word)
+    }
+}
+
+#[cfg(feature = "std")]
+impl SentenceSplitter for AbbreviationSentences {
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut merged = Vec::new();
+        // The start of a sentence which ended in an abbreviation.
+        let mut start = None;
+        for sentence in sentences(text) {
+            let range = sentence_range(text, sentence);
+            let first = *start.get_or_insert(range.start);
+            if !self.ends_with_abbreviation(sentence) {
+                merged.push(&text[first..range.end]);
+                start = None;
+            }
+        }
+        if let Some(start) = start {
+            merged.push(&text[start..]);
+        }
+        merged
+    }
+}
+
+/// Splits each line with another sentence splitter, so that no sentence spans a line break.
+struct LineSentences(Box<dyn SentenceSplitter>);
+
+impl SentenceSplitter for LineSentences {
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        text.split_inclusive('\n')
+            .flat_map(|line| self.0.split(line))
+            .collect()
+    }
+}
+
+/// Splits a sentence into words.
+pub trait Tokenizer: Send + Sync {
+    /// Split the sentence into words, in sentence order.
+    ///
+    /// Each word is a term before stemming,
+    /// e.g. punctuation and whitespace should typically be omitted.
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str>;
+}
+
+/// Splits words by the Unicode word boundary rules.
+///
+/// This is the default tokenizer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnicodeWords;
+
+impl Tokenizer for UnicodeWords {
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        s.unicode_words().collect()
+    }
+}
+
+/// Reduces a word to its stem, e.g. with a lemmatizer.
+///
+/// By default, the Snowball stemmer of the document's [`Language`] is used if it has one.
+pub trait Stem: Send + Sync {
+    /// The stem of the word, which is the term that the word contributes to a sentence.
+    fn stem(&self, word: &str) -> Box<str>;
+}
+
+/// A language agnostic stemmer, which truncates words to at most a given number of characters.
+///
+/// This is a crude but lightweight fallback for languages without a stemmer,
+/// since inflections typically only change the ends of words,
+/// e.g. a prefix of 5 characters suits many Slavic languages such as Polish or Czech.
+/// Shorter prefixes conflate more unrelated words, and longer prefixes fewer inflections.
+#[derive(Clone, Copy, Debug)]
+pub struct PrefixStemmer(pub NonZeroUsize);
+
+impl Stem for PrefixStemmer {
+    fn stem(&self, word: &str) -> Box<str> {
+        match word.char_indices().nth(self.0.get()) {
+            Some((end, _)) => word[..end].into(),
+            None => word.into(),
+        }
+    }
+}
+
+/// How terms are weighted when scoring sentences.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ScoringScheme {
+    /// Term frequency multiplied by inverse document frequency.
+    #[default]
+    TfIdf,
+    /// Okapi BM25, which saturates term frequency
+    /// and normalizes it by sentence length.
+    ///
+    /// Typical values are `k1 = 1.2` and `b = 0.75`.
+    Bm25 {
+        /// Controls how quickly term frequency saturates.
+        k1: f64,
+        /// Controls how strongly term frequency is normalized by sentence length,
+        /// from `0.0` (not at all) to `1.0` (fully).
+        b: f64,
+    },
+}
+
+/// How sentences' scores are normalized by their length.
+///
+/// The length of a sentence is its number of words, including stop words.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum LengthNormalization {
+    /// Scores are not normalized.
+    #[default]
+    None,
+    /// Scores are divided by `sqrt(len)`.
+    Sqrt,
+    /// Scores are divided by `log2(len + 1)`.
+    Log,
+}
+
+impl LengthNormalization {
+    /// The divisor of the score of a sentence with `len` words,
+    /// which is at least 1.0 even for sentences without words.
+    fn divisor(self, len: usize) -> f64 {
+        let len = len.max(1) as f64;
+        match self {
+            Self::None => 1.0,
+            Self::Sqrt => sqrt(len),
+            Self::Log => log2(len + 1.0),
+        }
+    }
+}
+
+/// What sentences are ranked by their similarity to.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CentralityMode {
+    /// The "core" sentence, i.e. the sentence most similar to the whole document.
+    #[default]
+    CoreSentence,
+    /// The whole document,
+    /// which may suit diffuse documents whose "core" sentence is an outlier.
+    Document,
+    /// A weighted average of the two similarities,
+    /// from `0.0` (the "core" sentence) to `1.0` (the whole document).
+    Blend(f64),
+}
+
+/// A boost of the scores of sentences near the start and end of the document,
+/// see [`SummarizerBuilder::position_boost`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionBoost {
+    /// The fraction of the document at each end whose sentences are boosted.
+    pub fraction: f64,
+    /// How much the scores of the first and last sentences are increased,
+    /// relative to their unboosted scores.
+    pub magnitude: f64,
+}
+
+/// The order in which the sentences of a summary are returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum OutputOrder {
+    /// The order in which the sentences appear in the document.
+    #[default]
+    Document,
+    /// By descending relevance, i.e. the most relevant sentence first.
+    Relevance,
+}
+
+/// The scope of the term statistics used to weight terms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdfScope {
+    /// Weight terms by their statistics within each paragraph.
+    Paragraph,
+    /// Weight terms by their statistics within the whole document.
+    Document,
+}
+
+/// An error which can occur when summarizing a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SummarizeError {
+    /// The ratio is not in `0.0..=1.0`.
+    InvalidRatio,
+    /// The text is not valid in its encoding.
+    InvalidEncoding,
+    /// The number of weights is not the number of sentences.
+    WeightCount,
+}
+
+impl fmt::Display for SummarizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::InvalidRatio => "ratio must be in 0.0..=1.0",
+            Self::InvalidEncoding => "text must be valid UTF-8, or UTF-16 with a byte order mark",
+            Self::WeightCount => "there must be exactly one weight per sentence",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SummarizeError {}
+
+impl Scored<'_> {
+    /// Blend each sentence's score with its similarity to `target`.
+    fn blend(&mut self, target: &IdfMap, weight: f64) {
+        for (score, tf_idf) in self.scores.iter_mut().zip(&self.tf_idfs) {
+            let similarity = cosine_compare(target, tf_idf);
+            *score = weight * similarity + (1.0 - weight) * *score;
+        }
+    }
+
+    /// The indices of the candidate sentences, by descending score.
+    fn ranking(&self) -> Vec<usize> {
+        let mut indices = self.candidates.clone();
+        sort_by_score(&mut indices, &self.scores);
+        if self.core_first {
+            let position = indices.iter().position(|&i| i == self.core).unwrap();
+            indices[..=position].rotate_right(1);
+        }
+        indices
+    }
+
+    /// The indices of the `n` highest ranked sentences of the `ranking`, without duplicates,
+    /// along with the duplicates which were removed.
+    fn select(
+        &self,
+        mut ranking: Vec<usize>,
+        n: NonZeroU32,
+        dedup_threshold: f64,
+    ) -> (Vec<usize>, Vec<(usize, usize, f64)>) {
+        ranking.truncate(saturating_usize(n));
+        let duplicates = dedup(
+            &self.sentences,
+            &self.tf_idfs,
+            &mut ranking,
+            dedup_threshold,
+        );
+        (ranking, duplicates)
+    }
+}
+
+/// A text which has been scored once to be summarized repeatedly,
+/// from [`Summarizer::prepare`].
+///
+/// This is useful e.g. for letting a user interactively choose the length of a summary,
+/// without scoring the whole text again for every length.
+pub struct PreparedDocument<'a> {
+    scored: Option<Scored<'a>>,
+    /// The indices of the candidate sentences, by descending score.
+    ranking: Vec<usize>,
+    dedup_threshold: f64,
+    output_order: OutputOrder,
+}
+
+impl<'a> PreparedDocument<'a> {
+    /// Provide a `n` sentence summary for the text.
+    ///
+    /// The sentences are exactly those returned by [`Summarizer::summarize_sentences`]
+    /// for the summarizer which prepared the text.
+    #[must_use]
+    pub fn top(&self, n: NonZeroU32) -> Vec<&'a str> {
+        let Some(scored) = &self.scored else {
+            return Vec::new();
+        };
+        let ranking = self.ranking.iter().copied().take(saturating_usize(n));
+        let (mut indices, _) = scored.select(ranking.collect(), n, self.dedup_threshold);
+        if self.output_order == OutputOrder::Document {
+            indices.sort_unstable();
+        }
+        indices.into_iter().map(|i| scored.sentences[i]).collect()
+    }
+}
+
+/// Inverse document frequencies of stemmed terms,
+/// precomputed from a corpus by [`Summarizer::build_idf`].
+///
+/// A model can be persisted and loaded back without recomputing it,
+/// either with the `serde` feature, or by collecting its [terms](IdfModel::iter)
+/// and later rebuilding it from them with [`FromIterator`].
+/// Only the former keeps the average number of terms per sentence of the corpus,
+/// which normalizes term frequencies by length with [`ScoringScheme::Bm25`].
+/// Note that a model is only meaningful for a summarizer
+/// which stems words and filters stop words the same way.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdfModel {
+    idfs: IdfMap,
+    /// The average number of terms per sentence of the corpus, if known and not zero.
+    #[cfg_attr(feature = "serde", serde(default))]
+    avg_len: Option<f64>,
+}
+
+impl IdfModel {
+    /// The inverse document frequency of the (stemmed) term, if it appears in the corpus.
+    #[must_use]
+    pub fn get(&self, term: &str) -> Option<f64> {
+        self.idfs.get(term).copied()
+    }
+
+    /// The terms of the model, with their inverse document frequencies, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.idfs.iter().map(|(term, &idf)| (&**term, idf))
+    }
+
+    /// The number of terms in the model.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.idfs.len()
+    }
+
+    /// Whether the model has no terms.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.idfs.is_empty()
+    }
+}
+
+impl FromIterator<(String, f64)> for IdfModel {
+    fn from_iter<I: IntoIterator<Item = (String, f64)>>(iter: I) -> Self {
+        Self {
+            idfs: iter
+                .into_iter()
+                .map(|(term, idf)| (term.into_boxed_str(), idf))
+                .collect(),
+            avg_len: None,
+        }
+    }
+}
+
+/// Splits sentences into terms.
+struct Analyzer {
+    tokenizer: Arc<dyn Tokenizer>,
+    stop_words: StopWords,
+    stop_phrases: StopPhrases,
+    stemmer: Stemmer,
+    ngram: usize,
+}
+
+impl Analyzer {
+    /// Add stop phrases, which are split into words by the tokenizer
+    /// and matched like stop words.
+    fn extend_stop_phrases(&mut self, phrases: impl IntoIterator<Item = String>) {
+        for phrase in phrases {
+            let words: Vec<Box<str>> = self
+                .tokenizer
+                .tokenize(&phrase)
+                .into_iter()
+                .map(|word| self.stop_words.fold(word).into())
+                .collect();
+            if let Some(first) = words.first() {
+                let phrases = self.stop_phrases.phrases.entry(first.clone()).or_default();
+                phrases.push(words);
+            }
+        }
+    }
+
+    /// The terms of each of the sentences, as from [`Analyzer::terms_with`],
+    /// with each distinct word stemmed only once, or once per thread with `rayon`.
+    ///
+    /// Each sentence is tokenized exactly once, so the terms should be computed up front
+    /// and reused for every pass over the sentences, e.g. for both idfs and tf-idfs.
+    fn terms(&self, sentences: &[&str]) -> Vec<Vec<Box<str>>> {
+        #[cfg(feature = "rayon")]
+        {
+            // Each thread stems with its own cache, so that sentences are analyzed in parallel.
+            sentences
+                .par_iter()
+                .map_init(HashMap::default, |stems, sentence| {
+                    self.terms_cached(sentence, stems)
+                })
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            let mut stems = HashMap::default();
+            sentences
+                .iter()
+                .map(|sentence| self.terms_cached(sentence, &mut stems))
+                .collect()
+        }
+    }
+
+    /// The terms of the sentence, as from [`Analyzer::terms_with`],
+    /// looking up the stem of each word in `stems` before stemming it.
+    fn terms_cached<'a>(
+        &self,
+        sentence: &'a str,
+        stems: &mut HashMap<&'a str, Box<str>>,
+    ) -> Vec<Box<str>> {
+        self.terms_with(sentence, |word| {
+            stems
+                .entry(word)
+                .or_insert_with(|| self.stemmer.stem(word))
+                .clone()
+        })
+    }
+
+    /// The terms of the sentence, i.e. its stemmed words other than stop words
+    /// and words of stop phrases, and the runs of up to `ngram` such consecutive words.
+    fn terms_with<'a>(
+        &self,
+        sentence: &'a str,
+        mut stem: impl FnMut(&'a str) -> Box<str>,
+    ) -> Vec<Box<str>> {
+        let Self {
+            tokenizer,
+            stop_words,
+            stop_phrases,
+            stemmer: _,
+            ngram,
+        } = self;
+
+        let words = tokenizer.tokenize(sentence);
+        let in_phrase = stop_phrases.mask(&words, stop_words);
+        let mut terms = Vec::new();
+        // The up to `ngram - 1` terms preceding the current word, without stop words between them.
+        let mut run = Vec::<Box<str>>::new();
+        for (word, in_phrase) in words.into_iter().zip(in_phrase) {
+            if in_phrase || stop_words.contains(word) {
+                run.clear();
+                continue;
+            }
+            let term = stem(word);
+            if *ngram > 1 {
+                for i in 0..run.len() {
+                    let mut ngram = run[i..].join(" ");
+                    ngram.push(' ');
+                    ngram.push_str(&term);
+                    terms.push(ngram.into_boxed_str());
+                }
+                if run.len() == ngram - 1 {
+                    run.remove(0);
+                }
+                run.push(term.clone());
+            }
+            terms.push(term);
+        }
+        terms
+    }
+}
+
+/// Statistics about a summary, from [`Summarizer::summarize_sentences_with_stats`].
+///
+/// The counts are of the sentences exactly as they are returned,
+/// including any whitespace they contain, e.g. after their final punctuation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SummaryStats {
+    /// The number of sentences.
+    pub sentences: usize,
+    /// The number of Unicode words.
+    pub words: usize,
+    /// The number of extended grapheme clusters, i.e. user-perceived characters.
+    pub graphemes: usize,
+    /// The number of bytes.
+    pub bytes: usize,
+}
+
+/// Reusable allocations for [`Summarizer::summarize_sentences_with_scratch`].
+#[derive(Clone, Debug, Default)]
+pub struct SummarizeScratch {
+    idfs: IdfMap,
+    tf_idfs: Vec<IdfMap>,
+}
+
+impl SummarizeScratch {
+    /// Create a new, empty `SummarizeScratch`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Term weighting options, resolved against the statistics of a document.
+#[derive(Clone, Copy)]
+struct Weighting {
+    sublinear_tf: bool,
+    bm25: Option<Bm25>,
+}
+
+#[derive(Clone, Copy)]
+struct Bm25 {
+    k1: f64,
+    b: f64,
+    avg_len: f64,
+}
+
+/// The intermediate results of scoring a document's sentences.
+struct Scored<'a> {
+    sentences: Vec<&'a str>,
+    tf_idfs: Vec<IdfMap>,
+    /// The tf-idf vector of the whole document.
+    overall: IdfMap,
+    /// The indices of the sentences which may be selected, in document order.
+    candidates: Vec<usize>,
+    /// The index of the "core" sentence.
+    core: usize,
+    /// Whether the "core" sentence is ranked first regardless of its score.
+    core_first: bool,
+    /// The final score of each sentence, i.e. its similarity to the "core" sentence
+    /// (or the document) after all configured adjustments, which is unbounded.
+    scores: Vec<f64>,
+}
+
+struct Stemmer {
+    #[cfg(feature = "std")]
+    algorithm: Option<rust_stemmers::Stemmer>,
+    /// Without `std` there are no stemmers.
+    #[cfg(not(feature = "std"))]
+    algorithm: Option<NoStemmer>,
+    /// A custom stemmer, which takes precedence over `algorithm`.
+    custom: Option<Arc<dyn Stem>>,
+    case_sensitive: bool,
+    #[cfg(feature = "unicode-normalization")]
+    normalize: bool,
+}
+
+#[cfg(not(feature = "std"))]
+enum NoStemmer {}
+
+#[cfg(not(feature = "std"))]
+impl NoStemmer {
+    fn stem<'a>(&self, _: &'a str) -> Cow<'a, str> {
+        match *self {}
+    }
+}
+
+impl Stemmer {
+    #[cfg(feature = "std")]
+    fn algorithm(language: Language) -> Option<rust_stemmers::Stemmer> {
+        use rust_stemmers::Algorithm;
+
+        #[rustfmt::skip]
+        let algo = match language {
+            Language::Arabic     => Algorithm::Arabic,
+            Language::Danish     => Algorithm::Danish,
+            Language::Dutch      => Algorithm::Dutch,
+            Language::English    => Algorithm::English,
+            Language::Finnish    => Algorithm::Finnish,
+            Language::French     => Algorithm::French,
+            Language::German     => Algorithm::German,
+            Language::Greek      => Algorithm::Greek,
+            Language::Hungarian  => Algorithm::Hungarian,
+            Language::Italian    => Algorithm::Italian,
+            Language::Norwegian  => Algorithm::Norwegian,
+            Language::Portuguese => Algorithm::Portuguese,
+            Language::Romanian   => Algorithm::Romanian,
+            Language::Russian    => Algorithm::Russian,
+            Language::Spanish    => Algorithm::Spanish,
+            Language::Swedish    => Algorithm::Swedish,
+            Language::Tamil      => Algorithm::Tamil,
+            Language::Turkish    => Algorithm::Turkish,
+            _ => {
+                return None;
+            }
+        };
+        Some(rust_stemmers::Stemmer::create(algo))
+    }
+
+    fn stem(&self, s: &str) -> Box<str> {
+        let word = strip_bidi_controls(s);
+        #[cfg(feature = "unicode-normalization")]
+        let word = if self.normalize { nfc(word) } else { word };
+        let s = &*word;
+        let tmp: Cow<str>;
+        let s = if let Some(stemmer) = &self.custom {
+            tmp = Cow::Owned(stemmer.stem(s).into_string());
+            &tmp
+        } else if let Some(stemmer) = &self.algorithm {
+            tmp = stemmer.stem(s);
+            &tmp
+        } else {
+            s
+        };
+        if self.case_sensitive {
+            s.into()
+        } else {
+            s.to_lowercase().into_boxed_str()
+        }
+    }
+}
+
+/// Common Tamil function words, which the `stop-words` crate does not provide.
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+const TAMIL_STOP_WORDS: &[&str] = &[
+    "ஒரு", "ஓர்", "ஒரே", "என்று", "என்ற", "என்பது", "என்பதை", "என்னும்", "எனும்", "என", "எனவும்",
+    "எனப்படும்", "என்றும்", "மற்றும்", "மேலும்", "அல்லது", "எனவே", "எனினும்", "ஆனால்", "இதனால்",
+    "அதனால்", "தவிர", "பின்னர்", "பிறகு", "இந்த", "இந்தக்", "இந்தச்", "இந்தத்", "இந்தப்", "அந்த",
+    "அந்தக்", "அந்தச்", "அந்தத்", "அந்தப்", "எந்த", "இது", "இதன்", "இதில்", "இதை", "இதனை", "இதற்கு",
+    "இவை", "இவர்", "அது", "அதன்", "அதில்", "அதை", "அதற்கு", "அவை", "அதே", "நான்", "நாம்", "நாங்கள்",
+    "நீ", "நீங்கள்", "அவன்", "அவள்", "அவர்", "அவர்கள்", "அவரது", "தான்", "தன்", "தனது", "என்",
+    "எனக்கு", "உன்", "உள்ள", "உள்ளது", "உள்ளன", "இருந்து", "இருந்த", "இருந்தது", "இருக்கும்",
+    "ஆகும்", "ஆகிய", "ஆகியோர்", "கொண்டு", "கொண்ட", "வந்த", "வந்து", "வரும்", "வரை", "வரையில்",
+    "வேண்டும்", "போது", "போன்ற", "போல", "போல்", "பற்றி", "பற்றிய", "மீது", "மேல்", "பின்", "முதல்",
+    "பல", "பலரும்", "பல்வேறு", "சில", "மிக", "மிகவும்", "அதிக", "மட்டும்", "மட்டுமே", "இன்னும்",
+    "எல்லாம்", "வேறு", "மற்ற", "பிற", "விட", "சற்று", "இங்கு", "இங்கே", "அங்கு", "அங்கே", "இப்போது",
+    "அன்று", "ஏன்", "என்ன", "யார்", "எப்படி",
+];
+
+struct StopWords {
+    words: HashSet<Box<str>>,
+    case_sensitive: bool,
+    #[cfg(feature = "unicode-normalization")]
+    normalize: bool,
+}
+
+impl StopWords {
+    #[cfg(feature = "std")]
+    fn for_language(language: Language) -> Vec<String> {
+        use stop_words::LANGUAGE as Dict;
+
+        #[rustfmt::skip]
+        let lang = match language {
+            Language::Afrikaans  => Dict::Afrikaans,
+            Language::Arabic     => Dict::Arabic,
             Language::Armenian   => Dict::Armenian,
             Language::Basque     => Dict::Basque,
             Language::Bengali    => Dict::Bengali,
@@ -266,23 +3513,85 @@ impl StopWords {
             Language::Yoruba     => Dict::Yoruba,
             Language::Zulu       => Dict::Zulu,
             Language::Turkish    => Dict::Turkish,
-            Language::Tamil      => return Self(HashSet::default()),
+            Language::Tamil      => return TAMIL_STOP_WORDS.iter().map(|&word| word.into()).collect(),
         };
-        let set = stop_words::get(lang)
-            .into_iter()
-            .map(|x| x.to_lowercase().into_boxed_str())
-            .collect();
-        Self(set)
+        stop_words::get(lang)
+    }
+
+    fn fold<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        let s = strip_bidi_controls(s);
+        #[cfg(feature = "unicode-normalization")]
+        let s = if self.normalize { nfc(s) } else { s };
+        if self.case_sensitive {
+            s
+        } else {
+            Cow::Owned(s.to_lowercase())
+        }
     }
 
     fn contains(&self, s: &str) -> bool {
-        let s = s.to_lowercase();
-        self.0.contains(&*s)
+        self.words.contains(&*self.fold(s))
+    }
+
+    fn extend(&mut self, words: impl IntoIterator<Item = String>) {
+        for word in words {
+            let word = self.fold(&word).into();
+            self.words.insert(word);
+        }
+    }
+
+    fn remove(&mut self, s: &str) {
+        self.words.remove(&*self.fold(s));
+    }
+}
+
+/// Phrases which are ignored, like stop words, wherever all of their words occur consecutively.
+#[derive(Default)]
+struct StopPhrases {
+    /// The words of each phrase, folded as stop words, by the phrase's first word.
+    phrases: HashMap<Box<str>, Vec<Vec<Box<str>>>>,
+}
+
+impl StopPhrases {
+    /// Whether each of the words is part of a stop phrase.
+    fn mask(&self, words: &[&str], stop_words: &StopWords) -> Vec<bool> {
+        let mut mask = vec![false; words.len()];
+        if self.phrases.is_empty() {
+            return mask;
+        }
+        let words: Vec<_> = words.iter().map(|word| stop_words.fold(word)).collect();
+        for i in 0..words.len() {
+            let Some(phrases) = self.phrases.get(&*words[i]) else {
+                continue;
+            };
+            for phrase in phrases {
+                let matches = words[i..].len() >= phrase.len()
+                    && phrase.iter().zip(&words[i..]).all(|(a, b)| **a == **b);
+                if matches {
+                    mask[i..i + phrase.len()].fill(true);
+                }
+            }
+        }
+        mask
     }
 }
 
 /// A document's language.
-#[derive(Clone, Copy)]
+///
+/// A language can be parsed from its English name or its ISO 639 code,
+/// case-insensitively, and is displayed as its English name.
+///
+/// Every language has default stop words, but only Arabic, Danish, Dutch, English,
+/// Finnish, French, German, Greek, Hungarian, Italian, Norwegian, Portuguese, Romanian,
+/// Russian, Spanish, Swedish, Tamil, and Turkish have a stemmer (see [`Language::has_stemmer`]).
+/// Words of other languages are only lowercased, unless a [custom stemmer](SummarizerBuilder::custom_stemmer)
+/// such as [`PrefixStemmer`] is used.
+///
+/// There is deliberately no `Default` language, since a document can't be assumed
+/// to be in any particular language. Likewise a [`SummarizerBuilder`] has no language
+/// by default, and summarizes language agnostically until one is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Language {
     Afrikaans,
@@ -346,11 +3655,463 @@ pub enum Language {
     Zulu,
 }
 
+impl Language {
+    const ALL: [Self; 59] = [
+        Self::Afrikaans,
+        Self::Arabic,
+        Self::Armenian,
+        Self::Basque,
+        Self::Bengali,
+        Self::Breton,
+        Self::Bulgarian,
+        Self::Catalan,
+        Self::Chinese,
+        Self::Croatian,
+        Self::Czech,
+        Self::Danish,
+        Self::Dutch,
+        Self::English,
+        Self::Esperanto,
+        Self::Estonian,
+        Self::Finnish,
+        Self::French,
+        Self::Galician,
+        Self::German,
+        Self::Greek,
+        Self::Gujarati,
+        Self::Hausa,
+        Self::Hebrew,
+        Self::Hindi,
+        Self::Hungarian,
+        Self::Indonesian,
+        Self::Irish,
+        Self::Italian,
+        Self::Japanese,
+        Self::Korean,
+        Self::Kurdish,
+        Self::Latin,
+        Self::Latvian,
+        Self::Lithuanian,
+        Self::Malay,
+        Self::Marathi,
+        Self::Norwegian,
+        Self::Persian,
+        Self::Polish,
+        Self::Portuguese,
+        Self::Romanian,
+        Self::Russian,
+        Self::Slovak,
+        Self::Slovenian,
+        Self::Somali,
+        Self::Sotho,
+        Self::Spanish,
+        Self::Swahili,
+        Self::Swedish,
+        Self::Tagalog,
+        Self::Tamil,
+        Self::Thai,
+        Self::Turkish,
+        Self::Ukrainian,
+        Self::Urdu,
+        Self::Vietnamese,
+        Self::Yoruba,
+        Self::Zulu,
+    ];
+
+    /// Every supported language, in alphabetical order of their English names.
+    pub fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Find the language with the given ISO 639 code, case-insensitively.
+    ///
+    /// Both two-letter (ISO 639-1) codes such as "en",
+    /// and three-letter (ISO 639-2 or ISO 639-3) codes such as "eng" are accepted.
+    #[must_use]
+    pub fn from_iso639(code: &str) -> Option<Self> {
+        let code = code.to_ascii_lowercase();
+        // Bibliographic ISO 639-2 codes, and codes of individual languages.
+        let alias = match code.as_str() {
+            "arm" => Some(Self::Armenian),
+            "baq" => Some(Self::Basque),
+            "chi" => Some(Self::Chinese),
+            "cze" => Some(Self::Czech),
+            "dut" => Some(Self::Dutch),
+            "fre" => Some(Self::French),
+            "ger" => Some(Self::German),
+            "gre" => Some(Self::Greek),
+            "may" => Some(Self::Malay),
+            "nb" | "nob" => Some(Self::Norwegian),
+            "per" => Some(Self::Persian),
+            "rum" => Some(Self::Romanian),
+            "slo" => Some(Self::Slovak),
+            _ => None,
+        };
+        alias.or_else(|| {
+            Self::all().find(|language| {
+                let (iso639_1, iso639_3) = language.codes();
+                code == iso639_1 || code == iso639_3
+            })
+        })
+    }
+
+    /// The two-letter ISO 639-1 code of the language, if it has one.
+    #[must_use]
+    pub fn iso639_1(&self) -> Option<&'static str> {
+        Some(self.codes().0)
+    }
+
+    /// The ISO 639-1 and ISO 639-3 codes of the language.
+    fn codes(self) -> (&'static str, &'static str) {
+        #[rustfmt::skip]
+        let codes = match self {
+            Self::Afrikaans  => ("af", "afr"),
+            Self::Arabic     => ("ar", "ara"),
+            Self::Armenian   => ("hy", "hye"),
+            Self::Basque     => ("eu", "eus"),
+            Self::Bengali    => ("bn", "ben"),
+            Self::Breton     => ("br", "bre"),
+            Self::Bulgarian  => ("bg", "bul"),
+            Self::Catalan    => ("ca", "cat"),
+            Self::Chinese    => ("zh", "zho"),
+            Self::Croatian   => ("hr", "hrv"),
+            Self::Czech      => ("cs", "ces"),
+            Self::Danish     => ("da", "dan"),
+            Self::Dutch      => ("nl", "nld"),
+            Self::English    => ("en", "eng"),
+            Self::Esperanto  => ("eo", "epo"),
+            Self::Estonian   => ("et", "est"),
+            Self::Finnish    => ("fi", "fin"),
+            Self::French     => ("fr", "fra"),
+            Self::Galician   => ("gl", "glg"),
+            Self::German     => ("de", "deu"),
+            Self::Greek      => ("el", "ell"),
+            Self::Gujarati   => ("gu", "guj"),
+            Self::Hausa      => ("ha", "hau"),
+            Self::Hebrew     => ("he", "heb"),
+            Self::Hindi      => ("hi", "hin"),
+            Self::Hungarian  => ("hu", "hun"),
+            Self::Indonesian => ("id", "ind"),
+            Self::Irish      => ("ga", "gle"),
+            Self::Italian    => ("it", "ita"),
+            Self::Japanese   => ("ja", "jpn"),
+            Self::Korean     => ("ko", "kor"),
+            Self::Kurdish    => ("ku", "kur"),
+            Self::Latin      => ("la", "lat"),
+            Self::Latvian    => ("lv", "lav"),
+            Self::Lithuanian => ("lt", "lit"),
+            Self::Malay      => ("ms", "msa"),
+            Self::Marathi    => ("mr", "mar"),
+            Self::Norwegian  => ("no", "nor"),
+            Self::Persian    => ("fa", "fas"),
+            Self::Polish     => ("pl", "pol"),
+            Self::Portuguese => ("pt", "por"),
+            Self::Romanian   => ("ro", "ron"),
+            Self::Russian    => ("ru", "rus"),
+            Self::Slovak     => ("sk", "slk"),
+            Self::Slovenian  => ("sl", "slv"),
+            Self::Somali     => ("so", "som"),
+            Self::Sotho      => ("st", "sot"),
+            Self::Spanish    => ("es", "spa"),
+            Self::Swahili    => ("sw", "swa"),
+            Self::Swedish    => ("sv", "swe"),
+            Self::Tagalog    => ("tl", "tgl"),
+            Self::Tamil      => ("ta", "tam"),
+            Self::Thai       => ("th", "tha"),
+            Self::Turkish    => ("tr", "tur"),
+            Self::Ukrainian  => ("uk", "ukr"),
+            Self::Urdu       => ("ur", "urd"),
+            Self::Vietnamese => ("vi", "vie"),
+            Self::Yoruba     => ("yo", "yor"),
+            Self::Zulu       => ("zu", "zul"),
+        };
+        codes
+    }
+
+    /// Whether the language has a stemmer,
+    /// which is used unless [disabled](SummarizerBuilder::stemmer).
+    ///
+    /// Without one, inflections of a word are distinct terms,
+    /// e.g. "kot" and "kota" in Polish, which makes summaries noisier.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn has_stemmer(&self) -> bool {
+        Stemmer::algorithm(*self).is_some()
+    }
+
+    /// The English name of the language, as it is displayed.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        #[rustfmt::skip]
+        let name = match self {
+            Self::Afrikaans  => "Afrikaans",
+            Self::Arabic     => "Arabic",
+            Self::Armenian   => "Armenian",
+            Self::Basque     => "Basque",
+            Self::Bengali    => "Bengali",
+            Self::Breton     => "Breton",
+            Self::Bulgarian  => "Bulgarian",
+            Self::Catalan    => "Catalan",
+            Self::Chinese    => "Chinese",
+            Self::Croatian   => "Croatian",
+            Self::Czech      => "Czech",
+            Self::Danish     => "Danish",
+            Self::Dutch      => "Dutch",
+            Self::English    => "English",
+            Self::Esperanto  => "Esperanto",
+            Self::Estonian   => "Estonian",
+            Self::Finnish    => "Finnish",
+            Self::French     => "French",
+            Self::Galician   => "Galician",
+            Self::German     => "German",
+            Self::Greek      => "Greek",
+            Self::Gujarati   => "Gujarati",
+            Self::Hausa      => "Hausa",
+            Self::Hebrew     => "Hebrew",
+            Self::Hindi      => "Hindi",
+            Self::Hungarian  => "Hungarian",
+            Self::Indonesian => "Indonesian",
+            Self::Irish      => "Irish",
+            Self::Italian    => "Italian",
+            Self::Japanese   => "Japanese",
+            Self::Korean     => "Korean",
+            Self::Kurdish    => "Kurdish",
+            Self::Latin      => "Latin",
+            Self::Latvian    => "Latvian",
+            Self::Lithuanian => "Lithuanian",
+            Self::Malay      => "Malay",
+            Self::Marathi    => "Marathi",
+            Self::Norwegian  => "Norwegian",
+            Self::Persian    => "Persian",
+            Self::Polish     => "Polish",
+            Self::Portuguese => "Portuguese",
+            Self::Romanian   => "Romanian",
+            Self::Russian    => "Russian",
+            Self::Slovak     => "Slovak",
+            Self::Slovenian  => "Slovenian",
+            Self::Somali     => "Somali",
+            Self::Sotho      => "Sotho",
+            Self::Spanish    => "Spanish",
+            Self::Swahili    => "Swahili",
+            Self::Swedish    => "Swedish",
+            Self::Tagalog    => "Tagalog",
+            Self::Tamil      => "Tamil",
+            Self::Thai       => "Thai",
+            Self::Turkish    => "Turkish",
+            Self::Ukrainian  => "Ukrainian",
+            Self::Urdu       => "Urdu",
+            Self::Vietnamese => "Vietnamese",
+            Self::Yoruba     => "Yoruba",
+            Self::Zulu       => "Zulu",
+        };
+        name
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseLanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let alias = match s.to_ascii_lowercase().as_str() {
+            "farsi" => Some(Self::Persian),
+            "filipino" => Some(Self::Tagalog),
+            _ => None,
+        };
+        alias
+            .or_else(|| Self::all().find(|language| language.name().eq_ignore_ascii_case(s)))
+            .or_else(|| Self::from_iso639(s))
+            .ok_or(ParseLanguageError)
+    }
+}
+
+/// An error which can occur when parsing a [`Language`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseLanguageError;
+
+impl fmt::Display for ParseLanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown language")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLanguageError {}
+
+/// Detect the most likely language of the text.
+///
+/// Returns `None` if the language can not be reliably detected,
+/// or if it is not a supported [`Language`].
+#[cfg(feature = "whatlang")]
+#[must_use]
+pub fn detect_language(text: &str) -> Option<Language> {
+    use whatlang::Lang;
+
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    let language = match info.lang() {
+        Lang::Afr => Language::Afrikaans,
+        Lang::Ara => Language::Arabic,
+        Lang::Hye => Language::Armenian,
+        Lang::Ben => Language::Bengali,
+        Lang::Bul => Language::Bulgarian,
+        Lang::Cat => Language::Catalan,
+        Lang::Cmn => Language::Chinese,
+        Lang::Hrv => Language::Croatian,
+        Lang::Ces => Language::Czech,
+        Lang::Dan => Language::Danish,
+        Lang::Nld => Language::Dutch,
+        Lang::Eng => Language::English,
+        Lang::Epo => Language::Esperanto,
+        Lang::Est => Language::Estonian,
+        Lang::Fin => Language::Finnish,
+        Lang::Fra => Language::French,
+        Lang::Deu => Language::German,
+        Lang::Ell => Language::Greek,
+        Lang::Guj => Language::Gujarati,
+        Lang::Heb => Language::Hebrew,
+        Lang::Hin => Language::Hindi,
+        Lang::Hun => Language::Hungarian,
+        Lang::Ind => Language::Indonesian,
+        Lang::Ita => Language::Italian,
+        Lang::Jpn => Language::Japanese,
+        Lang::Kor => Language::Korean,
+        Lang::Lat => Language::Latin,
+        Lang::Lav => Language::Latvian,
+        Lang::Lit => Language::Lithuanian,
+        Lang::Mar => Language::Marathi,
+        Lang::Nob => Language::Norwegian,
+        Lang::Pes => Language::Persian,
+        Lang::Pol => Language::Polish,
+        Lang::Por => Language::Portuguese,
+        Lang::Ron => Language::Romanian,
+        Lang::Rus => Language::Russian,
+        Lang::Slk => Language::Slovak,
+        Lang::Slv => Language::Slovenian,
+        Lang::Spa => Language::Spanish,
+        Lang::Swe => Language::Swedish,
+        Lang::Tgl => Language::Tagalog,
+        Lang::Tam => Language::Tamil,
+        Lang::Tha => Language::Thai,
+        Lang::Tur => Language::Turkish,
+        Lang::Ukr => Language::Ukrainian,
+        Lang::Urd => Language::Urdu,
+        Lang::Vie => Language::Vietnamese,
+        Lang::Zul => Language::Zulu,
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// The terms sorted by descending weight, with ties sorted alphabetically.
+fn sorted_terms(map: IdfMap) -> Vec<(String, f64)> {
+    let mut terms: Vec<_> = map.into_iter().collect();
+    terms.sort_unstable_by(|(a, x), (b, y)| y.total_cmp(x).then_with(|| a.cmp(b)));
+    terms
+        .into_iter()
+        .map(|(term, weight)| (term.into_string(), weight))
+        .collect()
+}
+
+/// Rank the sentences by descending score.
+fn rank<'a>(scored: Option<Scored<'a>>) -> (Vec<&'a str>, Vec<usize>, Vec<f64>) {
+    let Some(scored) = scored else {
+        return Default::default();
+    };
+    let indices = scored.ranking();
+    (scored.sentences, indices, scored.scores)
+}
+
+/// Convert `n` to a `usize`, saturating on targets where it doesn't fit,
+/// e.g. 16-bit targets, since no text has more than `usize::MAX` sentences or words anyway.
+fn saturating_usize(n: NonZeroU32) -> usize {
+    usize::try_from(n.get()).unwrap_or(usize::MAX)
+}
+
+/// Sort the indices by descending score,
+/// with ties broken by ascending index so that the order is reproducible.
+fn sort_by_score(indices: &mut [usize], scores: &[f64]) {
+    debug_assert!(indices.iter().all(|&i| !scores[i].is_nan()), "score is NaN");
+    indices.sort_unstable_by(|&i, &j| scores[j].total_cmp(&scores[i]).then(i.cmp(&j)));
+}
+
+/// Remove ranked sentences which are too similar to a higher ranked sentence.
+///
+/// Sentences with identical text are duplicates unless the threshold is above 1.0,
+/// even if their terms are too common to have any weight.
+///
+/// Returns the `(kept, dropped, similarity)` of each removed sentence,
+/// where `kept` is the highest ranked sentence it duplicates.
+fn dedup(
+    sentences: &[&str],
+    tf_idfs: &[IdfMap],
+    indices: &mut Vec<usize>,
+    threshold: f64,
+) -> Vec<(usize, usize, f64)> {
+    let mut kept: Vec<usize> = Vec::with_capacity(indices.len());
+    let mut duplicates = Vec::new();
+    for &i in &*indices {
+        match duplicate_of(sentences, tf_idfs, &kept, i, threshold) {
+            Some((j, similarity)) => duplicates.push((j, i, similarity)),
+            None => kept.push(i),
+        }
+    }
+    *indices = kept;
+    duplicates
+}
+
+/// The highest ranked of the `kept` sentences which the sentence at `i` duplicates,
+/// along with the similarity between them, as in [`dedup`].
+fn duplicate_of(
+    sentences: &[&str],
+    tf_idfs: &[IdfMap],
+    kept: &[usize],
+    i: usize,
+    threshold: f64,
+) -> Option<(usize, f64)> {
+    // Absorbs rounding error, so that identical sentences have a similarity of at least 1.0.
+    const TOLERANCE: f64 = 1e-9;
+
+    kept.iter().find_map(|&j| {
+        if threshold <= 1.0 && sentences[j].trim() == sentences[i].trim() {
+            return Some((j, 1.0));
+        }
+        let similarity = cosine_compare(&tf_idfs[j], &tf_idfs[i]);
+        (similarity + TOLERANCE >= threshold).then_some((j, similarity))
+    })
+}
+
+/// Select the sentences at the ranked `indices`, in the given order.
 #[inline(never)] // discourage monomorphization bloat
-fn summarize_impl(mut sentences: Vec<&str>, mut indices: Vec<u32>) -> Vec<&str> {
+fn summarize_impl(
+    mut sentences: Vec<&str>,
+    mut indices: Vec<usize>,
+    order: OutputOrder,
+) -> Vec<&str> {
+    if order == OutputOrder::Relevance {
+        return indices.into_iter().map(|i| sentences[i]).collect();
+    }
     indices.sort_unstable();
-    let end = *indices.last().unwrap() + 1;
-    sentences.truncate(end.try_into().unwrap());
+    let Some(&last) = indices.last() else {
+        return Vec::new();
+    };
+    debug_assert!(last < sentences.len(), "sentence index out of bounds");
+    debug_assert!(
+        indices.windows(2).all(|w| w[0] < w[1]),
+        "sentence selected more than once"
+    );
+    #[cfg(debug_assertions)]
+    let expected: Vec<_> = indices.iter().map(|&i| sentences[i]).collect();
+    let end = last + 1;
+    sentences.truncate(end);
 
     let mut indices = &*indices;
     let mut i = 0;
@@ -364,30 +4125,285 @@ fn summarize_impl(mut sentences: Vec<&str>, mut indices: Vec<u32>) -> Vec<&str>
         i += 1;
         keep
     });
+    #[cfg(debug_assertions)]
+    debug_assert!(
+        sentences
+            .iter()
+            .zip(&expected)
+            .all(|(a, b)| core::ptr::eq(*a, *b)),
+        "selected sentences are not the input sentences"
+    );
     sentences
 }
 
+/// Decode the text as UTF-8, or as UTF-16 if it has a byte order mark,
+/// borrowing it if it is UTF-8.
+fn decode(bytes: &[u8]) -> Option<Cow<'_, str>> {
+    let (bytes, big_endian) = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => return core::str::from_utf8(rest).ok().map(Cow::Borrowed),
+        [0xFF, 0xFE, rest @ ..] => (rest, false),
+        [0xFE, 0xFF, rest @ ..] => (rest, true),
+        _ => return core::str::from_utf8(bytes).ok().map(Cow::Borrowed),
+    };
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units = bytes.chunks_exact(2).map(|unit| {
+        let unit = [unit[0], unit[1]];
+        if big_endian {
+            u16::from_be_bytes(unit)
+        } else {
+            u16::from_le_bytes(unit)
+        }
+    });
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .ok()
+        .map(Cow::Owned)
+}
+
+/// Convert the string to Normalization Form C, unless it already is.
+#[cfg(feature = "unicode-normalization")]
+fn nfc(s: Cow<'_, str>) -> Cow<'_, str> {
+    use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+    if is_nfc_quick(s.chars()) == IsNormalized::Yes {
+        s
+    } else {
+        Cow::Owned(s.nfc().collect())
+    }
+}
+
+/// Remove the invisible bidirectional formatting characters from the word,
+/// borrowing it if it has none.
+///
+/// Word boundaries ignore these characters, so they can appear within words of
+/// right-to-left or mixed-direction text, but they must not make otherwise identical words distinct.
+fn strip_bidi_controls(word: &str) -> Cow<'_, str> {
+    fn is_bidi_control(c: char) -> bool {
+        matches!(
+            c,
+            '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+        )
+    }
+
+    if word.contains(is_bidi_control) {
+        Cow::Owned(word.chars().filter(|&c| !is_bidi_control(c)).collect())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
+
+/// Whether any word of the sentence contains a digit.
+fn has_number(sentence: &str) -> bool {
+    sentence
+        .unicode_words()
+        .any(|word| word.chars().any(char::is_numeric))
+}
+
+/// The byte range of `sentence` within `text`, which must contain it.
+fn sentence_range(text: &str, sentence: &str) -> Range<usize> {
+    let start = sentence.as_ptr() as usize - text.as_ptr() as usize;
+    start..start + sentence.len()
+}
+
+/// Trim the sentence, and collapse runs of whitespace within it to single spaces,
+/// only allocating if there are any to collapse.
+fn collapse_whitespace(sentence: &str) -> Cow<'_, str> {
+    let sentence = sentence.trim();
+    if !sentence
+        .split(' ')
+        .any(|word| word.is_empty() || word.contains(char::is_whitespace))
+    {
+        return Cow::Borrowed(sentence);
+    }
+    let mut collapsed = String::with_capacity(sentence.len());
+    for (i, word) in sentence.split_whitespace().enumerate() {
+        if i > 0 {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(word);
+    }
+    Cow::Owned(collapsed)
+}
+
+/// Split the text into paragraphs separated by blank lines.
+fn paragraphs(text: &str) -> Vec<&str> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                paragraphs.push(&text[start..offset]);
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        paragraphs.push(&text[start..]);
+    }
+    paragraphs
+}
+
+/// The visible text of an HTML document, with blocks separated by blank lines.
+#[cfg(feature = "html")]
+fn html_text(html: &str) -> String {
+    use html5gum::{DefaultEmitter, Token, Tokenizer};
+
+    #[rustfmt::skip]
+    const BLOCKS: &[&[u8]] = &[
+        b"address", b"article", b"aside", b"blockquote", b"br", b"dd", b"div", b"dl", b"dt",
+        b"figcaption", b"figure", b"footer", b"form", b"h1", b"h2", b"h3", b"h4", b"h5", b"h6",
+        b"header", b"hr", b"li", b"main", b"nav", b"ol", b"p", b"pre", b"section", b"table",
+        b"td", b"th", b"tr", b"ul",
+    ];
+    const HIDDEN: &[&[u8]] = &[b"script", b"style", b"template", b"title"];
+
+    let mut emitter = DefaultEmitter::default();
+    // Treat the contents of e.g. scripts as text, rather than as tags.
+    emitter.naively_switch_states(true);
+
+    let mut text = String::new();
+    let mut hidden = 0_usize;
+    for token in Tokenizer::new_with_emitter(html, emitter).flatten() {
+        let (name, start) = match &token {
+            Token::StartTag(tag) => (&*tag.name, !tag.self_closing),
+            Token::EndTag(tag) => (&*tag.name, false),
+            Token::String(s) => {
+                if hidden == 0 {
+                    let s = String::from_utf8_lossy(&s.value);
+                    for (i, word) in s.split(char::is_whitespace).enumerate() {
+                        if i > 0 && !text.ends_with(char::is_whitespace) {
+                            text.push(' ');
+                        }
+                        text.push_str(word);
+                    }
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if HIDDEN.contains(&&**name) {
+            if start {
+                hidden += 1;
+            } else if matches!(token, Token::EndTag(_)) {
+                hidden = hidden.saturating_sub(1);
+            }
+        } else if BLOCKS.contains(&&**name) && !text.ends_with("\n\n") {
+            text.push_str("\n\n");
+        }
+    }
+    text
+}
+
+/// A run of a Markdown document's plain text, and the source it was extracted from.
+#[cfg(feature = "markdown")]
+struct Segment {
+    plain: Range<usize>,
+    source: Range<usize>,
+    /// Whether the plain text is identical to its source,
+    /// e.g. it contains no escapes or entities.
+    verbatim: bool,
+}
+
+/// The plain text of a Markdown document, with blocks separated by blank lines.
+#[cfg(feature = "markdown")]
+fn markdown_text(md: &str) -> (String, Vec<Segment>) {
+    use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+    let mut plain = String::new();
+    let mut segments = Vec::new();
+    let mut code_blocks = 0_usize;
+    for (event, source) in Parser::new(md).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => code_blocks += 1,
+            Event::End(TagEnd::CodeBlock) => code_blocks -= 1,
+            Event::Text(text) | Event::Code(text) if code_blocks == 0 => {
+                let start = plain.len();
+                plain.push_str(&text);
+                segments.push(Segment {
+                    plain: start..plain.len(),
+                    verbatim: md[source.clone()] == *text,
+                    source,
+                });
+            }
+            Event::SoftBreak | Event::HardBreak => plain.push(' '),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::TableCell
+                | TagEnd::FootnoteDefinition,
+            ) => plain.push_str("\n\n"),
+            _ => {}
+        }
+    }
+    (plain, segments)
+}
+
+/// The range of the Markdown source which a range of its plain text was extracted from.
+///
+/// The range must start and end within text, not within the separators between text.
+#[cfg(feature = "markdown")]
+fn source_range(segments: &[Segment], plain: Range<usize>) -> Range<usize> {
+    let first = &segments[segments.partition_point(|s| s.plain.end <= plain.start)];
+    let last = &segments[segments.partition_point(|s| s.plain.end < plain.end)];
+    let start = if first.verbatim {
+        first.source.start + (plain.start - first.plain.start)
+    } else {
+        first.source.start
+    };
+    let end = if last.verbatim {
+        last.source.start + (plain.end - last.plain.start)
+    } else {
+        last.source.end
+    };
+    start..end
+}
+
 fn sentences(text: &str) -> Vec<&str> {
     text.unicode_sentences().collect()
 }
 
-fn tf_idfs(
-    sentences: &[&str],
+/// Compute the tf-idf vector of each sentence, given its terms, into `tf_idfs`,
+/// reusing its allocations.
+fn tf_idfs_into(
+    terms: &[Vec<Box<str>>],
     idfs: &IdfMap,
-    stop_words: &StopWords,
-    stemmer: &Stemmer,
-) -> Vec<IdfMap> {
-    sentences
-        .iter()
-        .copied()
-        .map(|sentence| tf_idf(&[sentence], idfs, stop_words, stemmer))
-        .collect()
+    weighting: Weighting,
+    tf_idfs: &mut Vec<IdfMap>,
+) {
+    tf_idfs.resize_with(terms.len(), IdfMap::default);
+
+    #[cfg(feature = "rayon")]
+    let pairs = tf_idfs.par_iter_mut().zip(terms.par_iter());
+    #[cfg(not(feature = "rayon"))]
+    let pairs = tf_idfs.iter_mut().zip(terms.iter());
+
+    pairs.for_each(|(tf_idf, terms)| {
+        tf_idf_into(core::slice::from_ref(terms), idfs, weighting, tf_idf);
+    });
 }
 
+/// A totally ordered score, for ranking with e.g. [`Iterator::max_by_key`].
 #[derive(Clone, Copy, PartialEq)]
 #[repr(transparent)]
 struct OrdFloat(f64);
 
+impl OrdFloat {
+    /// Wrap the score, which must not be NaN.
+    ///
+    /// NaN would be ordered consistently, but any NaN score is a bug,
+    /// e.g. a vector normalized by a magnitude of zero, which would silently corrupt a ranking.
+    fn new(x: f64) -> Self {
+        debug_assert!(!x.is_nan(), "score is NaN");
+        Self(x)
+    }
+}
+
 impl Eq for OrdFloat {}
 
 impl PartialOrd for OrdFloat {
@@ -402,6 +4418,22 @@ impl Ord for OrdFloat {
     }
 }
 
+/// The pairwise similarities of the sentences,
+/// with each sentence's similarity to itself set to zero.
+fn similarity_matrix(tf_idfs: &[IdfMap]) -> Vec<Vec<f64>> {
+    tf_idfs
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            tf_idfs
+                .iter()
+                .enumerate()
+                .map(|(j, b)| if i == j { 0.0 } else { cosine_compare(a, b) })
+                .collect()
+        })
+        .collect()
+}
+
 fn cosine_compare(a: &IdfMap, b: &IdfMap) -> f64 {
     let mut dotprod = 0.0;
     for (word, x) in a {
@@ -414,53 +4446,571 @@ fn cosine_compare(a: &IdfMap, b: &IdfMap) -> f64 {
     dotprod
 }
 
-fn tf_idf(sentences: &[&str], idfs: &IdfMap, stop_words: &StopWords, stemmer: &Stemmer) -> IdfMap {
-    let mut word_counts = HashMap::<_, u32>::new();
-    let words = sentences.iter().flat_map(|s| s.unicode_words());
-    for word in words {
-        if stop_words.contains(word) {
-            continue;
+/// The average number of terms per sentence.
+fn average_len(terms: &[Vec<Box<str>>]) -> f64 {
+    let len: usize = terms.iter().map(Vec::len).sum();
+    len as f64 / terms.len() as f64
+}
+
+/// The tf-idf vector of the sentences, given the terms of each of them.
+fn tf_idf(terms: &[Vec<Box<str>>], idfs: &IdfMap, weighting: Weighting) -> IdfMap {
+    let mut tf_idf = IdfMap::default();
+    tf_idf_into(terms, idfs, weighting, &mut tf_idf);
+    tf_idf
+}
+
+/// Compute the tf-idf vector of the sentences into `tf_idf`, reusing its allocation.
+fn tf_idf_into(terms: &[Vec<Box<str>>], idfs: &IdfMap, weighting: Weighting, tf_idf: &mut IdfMap) {
+    term_frequencies_into(terms, tf_idf);
+    weigh(tf_idf, idfs, weighting);
+}
+
+/// Count the frequencies of the sentences' terms into `tfs`, reusing its allocation.
+fn term_frequencies_into(terms: &[Vec<Box<str>>], tfs: &mut IdfMap) {
+    tfs.clear();
+    for term in terms.iter().flatten() {
+        *tfs.entry_ref(&**term).or_default() += 1.0;
+    }
+}
+
+/// Replace term frequencies by their tf-idf weights, normalized to a unit vector.
+fn weigh(tf_idf: &mut IdfMap, idfs: &IdfMap, weighting: Weighting) {
+    let len = tf_idf.values().sum::<f64>();
+    for (word, v) in tf_idf.iter_mut() {
+        let mut tf = *v;
+        if weighting.sublinear_tf {
+            tf = 1.0 + log2(tf);
         }
-        let word = stemmer.stem(word);
-        *word_counts.entry(word).or_default() += 1;
+        if let Some(Bm25 { k1, b, avg_len }) = weighting.bm25 {
+            tf = tf * (k1 + 1.0) / (tf + k1 * (1.0 - b + b * len / avg_len));
+        }
+        let idf = *idfs.get(word).unwrap_or(&0.0);
+        *v = tf * idf;
     }
-    let mut idf_map: IdfMap = word_counts
-        .into_iter()
-        .map(|(word, tf)| {
-            let tf = f64::from(tf);
-            let idf = *idfs.get(&word).unwrap_or(&0.0);
-            let tf_idf = tf * idf;
-            (word, tf_idf)
-        })
-        .collect();
-    let mag = idf_map.values().map(|x| x * x).sum::<f64>().sqrt();
-    for v in idf_map.values_mut() {
-        *v /= mag;
+    let mag = sqrt(tf_idf.values().map(|x| x * x).sum::<f64>());
+    // Without any weighted terms, e.g. if every term is in every sentence,
+    // the vector is left as zero rather than divided by zero.
+    if mag > 0.0 {
+        for v in tf_idf.values_mut() {
+            *v /= mag;
+        }
     }
-    idf_map
 }
 
-fn idfs(sentences: &[&str], stop_words: &StopWords, stemmer: &Stemmer) -> IdfMap {
-    let n = f64::from(u32::try_from(sentences.len()).unwrap());
-    let mut word_counts = HashMap::<_, u32>::new();
-    for sentence in sentences {
-        let mut set = HashSet::new();
-        for word in sentence.unicode_words() {
-            if stop_words.contains(word) {
-                continue;
+fn idfs(terms: &[Vec<Box<str>>], smooth: bool) -> IdfMap {
+    let mut idfs = IdfMap::default();
+    idfs_into(terms, smooth, &mut idfs);
+    idfs
+}
+
+/// Compute the inverse document frequencies of the sentences' terms into `idfs`,
+/// reusing its allocation.
+fn idfs_into(terms: &[Vec<Box<str>>], smooth: bool, idfs: &mut IdfMap) {
+    let n = terms.len() as f64;
+    let count = |mut counts: IdfMap, terms: &Vec<Box<str>>| {
+        let distinct: HashSet<&str> = terms.iter().map(|term| &**term).collect();
+        for word in distinct {
+            *counts.entry_ref(word).or_default() += 1.0;
+        }
+        counts
+    };
+
+    // Document frequencies are counted in place, and then replaced by their inverse.
+    idfs.clear();
+    #[cfg(feature = "rayon")]
+    {
+        // Each thread counts its own sentences, and the counts are merged at the end.
+        // The counts are integers, so they sum exactly in any order.
+        let counts =
+            terms
+                .par_iter()
+                .fold(IdfMap::default, count)
+                .reduce(IdfMap::default, |mut a, b| {
+                    for (word, df) in b {
+                        *a.entry(word).or_default() += df;
+                    }
+                    a
+                });
+        // Extended rather than replaced, to keep the allocation of a reused map.
+        idfs.extend(counts);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        *idfs = terms.iter().fold(core::mem::take(idfs), count);
+    }
+    for idf in idfs.values_mut() {
+        *idf = inverse_document_frequency(n, *idf, smooth);
+    }
+}
+
+/// The idf of a term in `df` of `n` sentences, see [`SummarizerBuilder::smooth_idf`].
+fn inverse_document_frequency(n: f64, df: f64, smooth: bool) -> f64 {
+    if smooth {
+        log2((n + 1.0) / (df + 1.0)) + 1.0
+    } else {
+        log2(n / df)
+    }
+}
+
+#[cfg(feature = "std")]
+fn abs(x: f64) -> f64 {
+    x.abs()
+}
+
+#[cfg(not(feature = "std"))]
+fn abs(x: f64) -> f64 {
+    libm::fabs(x)
+}
+
+#[cfg(feature = "std")]
+fn log2(x: f64) -> f64 {
+    x.log2()
+}
+
+#[cfg(not(feature = "std"))]
+fn log2(x: f64) -> f64 {
+    libm::log2(x)
+}
+
+#[cfg(feature = "std")]
+fn round(x: f64) -> f64 {
+    x.round()
+}
+
+#[cfg(not(feature = "std"))]
+fn round(x: f64) -> f64 {
+    libm::round(x)
+}
+
+#[cfg(feature = "std")]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn n(n: u32) -> NonZeroU32 {
+        NonZeroU32::new(n).unwrap()
+    }
+
+    #[test]
+    fn streaming_matches_summarize_sentences_at_every_split() {
+        let summarizer = SummarizerBuilder::new().build();
+        let text = "\"Cats purr.\" Dogs bark loudly. Cats and dogs sleep. \"Birds sing!\"";
+        let expected = summarizer.summarize_sentences(text, n(2));
+        for i in (0..=text.len()).filter(|&i| text.is_char_boundary(i)) {
+            let mut streaming =
+                StreamingSummarizer::new(&summarizer, NonZeroUsize::new(2).unwrap());
+            streaming.push(&text[..i]);
+            streaming.push(&text[i..]);
+            assert_eq!(streaming.finish(n(2)), expected, "split at {i}");
+        }
+    }
+
+    #[test]
+    fn junk_sentences_are_never_selected() {
+        let summarizer = SummarizerBuilder::new().build();
+        let text = "42. ... Cats purr softly. 7!";
+        assert_eq!(
+            summarizer.summarize_sentences(text, n(4)),
+            ["Cats purr softly. "]
+        );
+        assert!(summarizer
+            .summarize_sentences("42. ... 7!", n(4))
+            .is_empty());
+    }
+
+    #[test]
+    fn all_stop_word_document() {
+        let summarizer = SummarizerBuilder::new()
+            .stop_words(["it", "is"].map(String::from))
+            .build();
+        let text = "It is. Is it? It is it.";
+        assert!(summarizer.summarize_sentences(text, n(2)).is_empty());
+        assert!(summarizer.summarize_ranked(text).is_empty());
+        assert_eq!(summarizer.core_sentence(text), None);
+        assert!(summarizer.keywords(text, 3).is_empty());
+        // Without any candidates, the sentences are taken in document order.
+        assert_eq!(
+            summarizer.summarize_sentences_filled(text, n(2)),
+            ["It is. ", "Is it? "]
+        );
+        let (_, matrix) = summarizer.similarity_matrix(text);
+        assert!(matrix.iter().flatten().all(|&similarity| similarity == 0.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stop_word_sentences_summarize_to_nothing() {
+        let summarizer = Summarizer::new(Language::English);
+        let text = "It is what it is. And so it was.";
+        assert!(summarizer.summarize_sentences(text, n(1)).is_empty());
+        assert!(summarizer.summarize_ratio(text, 0.5).is_empty());
+        assert!(summarizer.summarize_words(text, n(10)).is_empty());
+    }
+
+    #[test]
+    fn identical_sentences_return_the_first() {
+        let summarizer = SummarizerBuilder::new().build();
+        let text = "Hi. Hi. Hi.";
+        for n in [n(1), n(3)] {
+            let summary = summarizer.summarize_sentences(text, n);
+            assert_eq!(summary, ["Hi. "]);
+            assert_eq!(summary[0].as_ptr(), text.as_ptr());
+        }
+    }
+
+    #[test]
+    fn identical_sentences_are_dropped_by_every_summary() {
+        let summarizer = SummarizerBuilder::new().build();
+        let text = "Hi. Hi. Hi.";
+        let expected = ["Hi. "];
+        assert_eq!(summarizer.summarize_scored(text, n(3)).len(), 1);
+        assert_eq!(summarizer.summarize_ratio(text, 1.0), expected);
+        assert_eq!(
+            summarizer.summarize_ratio_clamped(text, 1.0, 3, 3),
+            expected
+        );
+        assert_eq!(summarizer.summarize_words(text, n(3)), expected);
+        assert_eq!(summarizer.summarize_grapheme_budget(text, 9), expected);
+        assert_eq!(summarizer.summarize_threshold(text, 0.0), expected);
+        let idf = summarizer.build_idf(&["Hi there.", "Bye."]);
+        assert_eq!(
+            summarizer.summarize_sentences_with_idf(text, n(3), &idf),
+            expected
+        );
+        assert_eq!(summarizer.summarize_query(text, "hi", n(3)), expected);
+        assert_eq!(
+            summarizer.summarize_per_paragraph(text, n(3), IdfScope::Paragraph),
+            expected
+        );
+        assert_eq!(
+            summarizer.summarize_textrank(text, n(3), 0.85, 100),
+            expected
+        );
+        assert_eq!(summarizer.summarize_coverage(text, n(3)), expected);
+        // Unless duplicates are kept.
+        let summarizer = SummarizerBuilder::new().dedup_threshold(1.1).build();
+        assert_eq!(summarizer.summarize_scored(text, n(3)).len(), 3);
+        assert_eq!(summarizer.summarize_words(text, n(3)).len(), 3);
+    }
+
+    #[test]
+    fn unbiased_summary_matches_summarize_sentences() {
+        let summarizer = SummarizerBuilder::new().build();
+        for text in [
+            "Hi. Hi. Hi.",
+            "Cats purr. Dogs bark. Cats and dogs sleep. Cats purr.",
+        ] {
+            for n in [n(1), n(3)] {
+                assert_eq!(
+                    summarizer.summarize_sentences_biased(text, n, 0.0),
+                    summarizer.summarize_sentences(text, n)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mmr_without_redundancy_matches_summarize_sentences() {
+        let text = "Rust is fast. Rust is a systems language that is fast and safe. \
+            Rust is safe. Rust is fast.";
+        for core_first in [false, true] {
+            let summarizer = SummarizerBuilder::new()
+                .position_boost(0.34, 10.0)
+                .always_include_core(core_first)
+                .build();
+            for n in [n(1), n(2), n(4)] {
+                assert_eq!(
+                    summarizer.summarize_sentences_mmr(text, n, 1.0),
+                    summarizer.summarize_sentences(text, n)
+                );
             }
-            let word = stemmer.stem(word);
-            set.insert(word);
         }
-        for word in set {
-            *word_counts.entry(word).or_default() += 1;
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn newlines_as_breaks_split_abbreviations_at_line_ends() {
+        let text = "- buy milk, eggs etc.\n- walk the dog\n\n- write code\n";
+        let builder = SummarizerBuilder::new()
+            .language(Language::English)
+            .merge_abbreviations(true);
+        assert_eq!(
+            builder.clone().build().split_sentences(text),
+            ["- buy milk, eggs etc.\n- walk the dog\n", "- write code\n"]
+        );
+        let expected = [
+            "- buy milk, eggs etc.\n",
+            "- walk the dog\n",
+            "- write code\n",
+        ];
+        assert_eq!(
+            builder
+                .treat_newlines_as_breaks(true)
+                .build()
+                .split_sentences(text),
+            expected
+        );
+        // Without merging abbreviations, lines are separate sentences either way.
+        for enabled in [false, true] {
+            let summarizer = SummarizerBuilder::new()
+                .treat_newlines_as_breaks(enabled)
+                .build();
+            assert_eq!(summarizer.split_sentences(text), expected);
+        }
+    }
+
+    #[test]
+    fn scratch_keeps_its_allocations_without_candidates() {
+        let summarizer = SummarizerBuilder::new().build();
+        let mut scratch = SummarizeScratch::new();
+        let summary = summarizer.summarize_sentences_with_scratch(
+            "Cats purr. Dogs bark.",
+            n(1),
+            &mut scratch,
+        );
+        assert_eq!(summary.len(), 1);
+        assert!(scratch.tf_idfs.capacity() >= 2);
+        let summary = summarizer.summarize_sentences_with_scratch("42. 7.", n(1), &mut scratch);
+        assert!(summary.is_empty());
+        assert!(scratch.tf_idfs.capacity() >= 2);
+    }
+
+    #[test]
+    fn invalid_ratio_is_an_error() {
+        let summarizer = SummarizerBuilder::new().build();
+        let text = "Cats purr. Dogs bark.";
+        for ratio in [-0.1, 1.1, f64::NAN] {
+            assert_eq!(
+                summarizer.try_summarize_ratio(text, ratio),
+                Err(SummarizeError::InvalidRatio)
+            );
+            assert_eq!(
+                summarizer.try_summarize_ratio_strict(text, ratio),
+                Err(SummarizeError::InvalidRatio)
+            );
+        }
+        assert!(summarizer.try_summarize_ratio(text, 1.0).is_ok());
+    }
+
+    #[test]
+    fn bm25_similarity_is_independent_of_the_pair() {
+        let summarizer = SummarizerBuilder::new()
+            .scoring(ScoringScheme::Bm25 { k1: 1.2, b: 0.75 })
+            .build();
+        let idf = summarizer.build_idf(&["Cats cats purr. Dogs bark loudly at night. Cats nap."]);
+        let vector = |sentence| -> IdfMap {
+            summarizer
+                .sentence_vector(sentence, &idf)
+                .into_iter()
+                .map(|(term, weight)| (term.into_boxed_str(), weight))
+                .collect()
+        };
+        let a = "Cats cats purr.";
+        for b in ["Cats nap.", "Dogs bark loudly at night, and cats nap."] {
+            let expected = cosine_compare(&vector(a), &vector(b));
+            let similarity = summarizer.sentence_similarity(a, b, &idf);
+            assert!(
+                (similarity - expected).abs() < 1e-9,
+                "{similarity} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn single_sentence_is_returned_for_any_n() {
+        let summarizer = SummarizerBuilder::new().build();
+        for text in ["Cats purr.", "Cats purr softly when they are content. "] {
+            for n in [n(1), n(2), NonZeroU32::MAX] {
+                assert_eq!(summarizer.summarize_sentences(text, n), [text]);
+                assert_eq!(summarizer.summarize_sentences_filled(text, n), [text]);
+            }
+        }
+    }
+
+    const ARABIC: &str = "\u{200F}القطط تنام كثيرا في النهار.\u{200F} \
+        القطط تحب الشمس الدافئة في النهار؟ الكلاب تنبح في الليل. هل تنام الكلاب في النهار؟";
+    const HEBREW: &str = "\u{200F}החתולים ישנים הרבה ביום. החתולים אוהבים את השמש החמה ביום. \
+        הכלבים נובחים בלילה.\u{200F} האם הכלבים ישנים ביום?";
+
+    #[test]
+    fn right_to_left_sentences_split_whole() {
+        let summarizer = SummarizerBuilder::new().build();
+        assert_eq!(
+            summarizer.split_sentences(ARABIC),
+            [
+                "\u{200F}القطط تنام كثيرا في النهار.\u{200F} ",
+                "القطط تحب الشمس الدافئة في النهار؟ ",
+                "الكلاب تنبح في الليل. ",
+                "هل تنام الكلاب في النهار؟",
+            ]
+        );
+        assert_eq!(
+            summarizer.split_sentences(HEBREW),
+            [
+                "\u{200F}החתולים ישנים הרבה ביום. ",
+                "החתולים אוהבים את השמש החמה ביום. ",
+                "הכלבים נובחים בלילה.\u{200F} ",
+                "האם הכלבים ישנים ביום?",
+            ]
+        );
+        // Directionality marks are kept, rather than split from or into words.
+        let keywords = summarizer.keywords(ARABIC, 100);
+        assert!(keywords.iter().any(|(keyword, _)| keyword == "القطط"));
+        assert!(keywords
+            .iter()
+            .all(|(keyword, _)| !keyword.contains('\u{200F}')));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn right_to_left_sentences_summarize() {
+        for (text, language) in [(ARABIC, Language::Arabic), (HEBREW, Language::Hebrew)] {
+            let summarizer = Summarizer::new(language);
+            let sentences = summarizer.split_sentences(text);
+            let summary = summarizer.summarize_sentences(text, n(2));
+            assert_eq!(summary, sentences[..2]);
+        }
+    }
+
+    #[test]
+    fn always_include_core_when_outranked() {
+        let text = "Rust is fast. Rust is a systems language that is fast and safe. Rust is safe.";
+        let builder = SummarizerBuilder::new().position_boost(0.34, 10.0);
+        let summarizer = builder.clone().build();
+        let core = summarizer.core_sentence(text).unwrap();
+        assert!(!summarizer.summarize_sentences(text, n(1)).contains(&core));
+
+        let summarizer = builder.always_include_core(true).build();
+        for n in [n(1), n(2), n(3)] {
+            assert!(summarizer.summarize_sentences(text, n).contains(&core));
+            assert!(summarizer.prepare(text).top(n).contains(&core));
+        }
+    }
+
+    struct Whitespace;
+
+    impl Tokenizer for Whitespace {
+        fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+            s.split_whitespace().collect()
+        }
+    }
+
+    #[test]
+    fn word_budget_counts_with_tokenizer() {
+        let summarizer = SummarizerBuilder::new().tokenizer(Whitespace).build();
+        // Four words for the tokenizer, but five Unicode words.
+        let text = "Cats purr-purr. Cats nap.";
+        assert_eq!(
+            summarizer.summarize_words(text, n(4)),
+            ["Cats purr-purr. ", "Cats nap."]
+        );
+    }
+
+    #[test]
+    fn min_sentence_words_counts_with_tokenizer() {
+        let summarizer = SummarizerBuilder::new()
+            .tokenizer(Whitespace)
+            .min_sentence_words(2)
+            .build();
+        // Four Unicode words, but a single word to the tokenizer.
+        let text = "State-of-the-art. Cats purr softly.";
+        assert_eq!(
+            summarizer.summarize_sentences(text, n(2)),
+            ["Cats purr softly."]
+        );
+    }
+
+    const BYLINE: &str = "By Jane Doe, Reuters.\n\n\
+        Cats sleep a lot. Cats sleep in the sun.\n\n\
+        Dogs bark at night. Dogs bark at the cats.";
+
+    fn trimmed(sentences: Vec<&str>) -> Vec<&str> {
+        sentences.into_iter().map(str::trim_end).collect()
+    }
+
+    #[test]
+    fn skip_leading_per_paragraph_only_skips_document_start() {
+        let summarizer = SummarizerBuilder::new().skip_leading(1).build();
+        for idf in [IdfScope::Paragraph, IdfScope::Document] {
+            let summary = summarizer.summarize_per_paragraph(BYLINE, n(2), idf);
+            assert_eq!(
+                trimmed(summary),
+                [
+                    "Cats sleep a lot.",
+                    "Cats sleep in the sun.",
+                    "Dogs bark at night.",
+                    "Dogs bark at the cats.",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn skip_leading_filled_never_selects_skipped() {
+        let summarizer = SummarizerBuilder::new().skip_leading(1).build();
+        let summary = summarizer.summarize_sentences_filled(BYLINE, n(5));
+        assert_eq!(summary.len(), 4);
+        assert!(!summary.iter().any(|sentence| sentence.starts_with("By")));
+
+        // Without any candidates, the summary falls back to the unranked sentences.
+        let summarizer = SummarizerBuilder::new()
+            .skip_leading(1)
+            .min_sentence_words(100)
+            .build();
+        let summary = summarizer.summarize_sentences_filled(BYLINE, n(2));
+        assert_eq!(
+            trimmed(summary),
+            ["Cats sleep a lot.", "Cats sleep in the sun."]
+        );
+    }
+
+    /// The zero-based indices of the summary's sentences among the text's sentences.
+    fn positions(sentences: &[&str], summary: &[&str]) -> Vec<usize> {
+        summary
+            .iter()
+            .map(|selected| {
+                sentences
+                    .iter()
+                    .position(|sentence| {
+                        sentence.as_ptr() == selected.as_ptr() && sentence.len() == selected.len()
+                    })
+                    .expect("summary sentence is not a sentence of the text")
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn summary_is_ordered_subset_of_sentences(
+            text in prop_oneof![
+                "(([a-e]{1,3}|42) {1,2}){1,6}[.!?]( |\n|\n\n)?",
+                "((([a-e]{1,3}|42) {1,2}){1,6}[.!?]( |\n|\n\n)){0,12}",
+                any::<String>(),
+            ],
+            n in 1..8_u32,
+        ) {
+            let summarizer = SummarizerBuilder::new().build();
+            let sentences = summarizer.split_sentences(&text);
+            let n = NonZeroU32::new(n).unwrap();
+            for summary in [
+                summarizer.summarize_sentences(&text, n),
+                summarizer.summarize_sentences_filled(&text, n),
+            ] {
+                prop_assert!(summary.len() <= saturating_usize(n));
+                let positions = positions(&sentences, &summary);
+                // Strictly increasing, i.e. in document order without duplicates.
+                prop_assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+            }
         }
     }
-    word_counts
-        .into_iter()
-        .map(|(word, count)| {
-            let idf = (n / f64::from(count)).log2();
-            (word, idf)
-        })
-        .collect()
 }